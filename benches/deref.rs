@@ -0,0 +1,51 @@
+//! Compare dereferencing a `RentToOwn<T>` in a hot loop against unwrapping an
+//! `&mut Option<T>`, which is what `RentToOwn<T>` used to do under the covers.
+//!
+//! Run with `cargo bench`.
+
+use rent_to_own::RentToOwn;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITERS: u64 = 100_000_000;
+
+fn bench<F: FnMut() -> u64>(name: &str, mut f: F) {
+    // Warm up.
+    black_box(f());
+
+    let mut best = Duration::MAX;
+    for _ in 0..5 {
+        let start = Instant::now();
+        black_box(f());
+        best = best.min(start.elapsed());
+    }
+
+    println!(
+        "{:<20} {:>8.3} ns/iter",
+        name,
+        best.as_nanos() as f64 / ITERS as f64
+    );
+}
+
+fn main() {
+    bench("Option::unwrap", || {
+        let mut inner = Some(1u64);
+        let inner = &mut &mut inner;
+        let mut sum = 0;
+        for _ in 0..ITERS {
+            sum += black_box(&*inner).as_ref().unwrap();
+        }
+        sum
+    });
+
+    bench("RentToOwn::deref", || {
+        let (_, sum) = RentToOwn::with(1u64, |inner| {
+            let mut sum = 0;
+            for _ in 0..ITERS {
+                sum += **black_box(&*inner);
+            }
+            sum
+        });
+        sum
+    });
+}
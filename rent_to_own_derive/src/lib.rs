@@ -0,0 +1,351 @@
+/*!
+
+`#[derive(Rentable)]`: generate per-field `RentToOwn` accessors.
+
+For every field of type `Option<T>` in a struct, deriving `Rentable` generates
+a `rent_<field>` method that gives a closure the option to take ownership of
+the field's value, via `RentToOwn::with`. If the closure takes ownership, the
+field is left as `None`, otherwise the value is put back into the field. Fields
+whose type is not an `Option` are left alone.
+
+This crate is re-exported by `rent_to_own` when its `derive` feature is
+enabled, which is the recommended way to use it.
+
+## Example
+
+```
+use rent_to_own::RentToOwn;
+use rent_to_own_derive::Rentable;
+
+struct Socket;
+
+#[derive(Rentable)]
+struct Connection {
+    socket: Option<Socket>,
+    retries: u32,
+}
+
+fn maybe_claim<'a>(socket: &'a mut RentToOwn<'a, Socket>, claim: bool) -> Option<Socket> {
+    if claim {
+        Some(socket.take())
+    } else {
+        None
+    }
+}
+
+let mut conn = Connection { socket: Some(Socket), retries: 0 };
+
+// The closure doesn't take the socket, so it is put back into the field.
+let claimed = conn.rent_socket(|s| maybe_claim(s, false));
+assert!(matches!(claimed, Some(None)));
+assert!(conn.socket.is_some());
+
+// The closure takes the socket, so the field is left empty.
+let claimed = conn.rent_socket(|s| maybe_claim(s, true));
+assert!(matches!(claimed, Some(Some(Socket))));
+assert!(conn.socket.is_none());
+
+// There is no socket left to rent, so the closure isn't called.
+assert!(conn.rent_socket(|s| maybe_claim(s, true)).is_none());
+```
+
+Generated methods have the same visibility as their field.
+
+ */
+
+#![deny(missing_docs)]
+#![deny(missing_debug_implementations)]
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+use std::iter::Peekable;
+
+/// Derive `rent_<field>` accessors for each `Option` field of a struct.
+///
+/// See the crate level documentation for details and examples.
+#[proc_macro_derive(Rentable)]
+pub fn derive_rentable(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output,
+        Err(msg) => format!("compile_error!({:?});", msg).parse().unwrap(),
+    }
+}
+
+/// The generics of the struct being derived, as source text.
+struct Generics {
+    /// The generic parameters, with their bounds but without their defaults,
+    /// for the `impl<...>` header.
+    params: String,
+    /// The generic parameters' names, for the `Name<...>` type.
+    args: String,
+}
+
+/// A field whose type is `Option<ty>`.
+struct OptionField {
+    vis: String,
+    name: String,
+    ty: String,
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, String> {
+    let mut tokens = input.into_iter().peekable();
+
+    skip_attributes(&mut tokens);
+    parse_visibility(&mut tokens);
+
+    match tokens.next() {
+        Some(TokenTree::Ident(ref i)) if i.to_string() == "struct" => {}
+        _ => return Err("`#[derive(Rentable)]` only supports structs".into()),
+    }
+
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(i)) => i.to_string(),
+        _ => return Err("expected a struct name".into()),
+    };
+
+    let generics = match tokens.peek() {
+        Some(TokenTree::Punct(p)) if p.as_char() == '<' => {
+            tokens.next();
+            parse_generics(&mut tokens)?
+        }
+        _ => Generics {
+            params: String::new(),
+            args: String::new(),
+        },
+    };
+
+    let mut where_clause = vec![];
+    let body = loop {
+        match tokens.next() {
+            Some(TokenTree::Group(ref g)) if g.delimiter() == Delimiter::Brace => break g.stream(),
+            Some(TokenTree::Group(ref g)) if g.delimiter() == Delimiter::Parenthesis => {
+                return Err("`#[derive(Rentable)]` does not support tuple structs".into())
+            }
+            Some(TokenTree::Punct(ref p)) if p.as_char() == ';' => {
+                return Err("`#[derive(Rentable)]` does not support unit structs".into())
+            }
+            Some(tt) => where_clause.push(tt),
+            None => return Err("expected struct fields".into()),
+        }
+    };
+    let where_clause = stringify(where_clause);
+
+    let methods: String = parse_option_fields(body)?
+        .iter()
+        .map(generate_method)
+        .collect();
+
+    let output = format!(
+        "impl<{params}> {name}<{args}> {where_clause} {{ {methods} }}",
+        params = generics.params,
+        name = name,
+        args = generics.args,
+        where_clause = where_clause,
+        methods = methods,
+    );
+    // Parsing only lexes the output, which we built from already-lexed
+    // tokens, so this can't fail. Whether it's valid Rust is up to rustc.
+    Ok(output
+        .parse()
+        .expect("`#[derive(Rentable)]` output should lex"))
+}
+
+fn generate_method(field: &OptionField) -> String {
+    let method = format!("rent_{}", field.name.trim_start_matches("r#"));
+    let doc = format!(
+        "Give the function `f` the option to take ownership of the `{field}` \
+         field's value.\n\n\
+         If `f` takes ownership, `{field}` is left as `None`, otherwise the \
+         value is put back. Returns `None`, without calling `f`, if `{field}` is \
+         already `None`.",
+        field = field.name.trim_start_matches("r#"),
+    );
+    format!(
+        "#[doc = {doc:?}]
+        #[allow(dead_code)]
+        {vis} fn {method}<__RentF, __RentU>(&mut self, f: __RentF) -> ::std::option::Option<__RentU>
+        where
+            __RentF: for<'__rent> ::std::ops::FnOnce(
+                &'__rent mut ::rent_to_own::RentToOwn<'__rent, {ty}>,
+            ) -> __RentU,
+        {{
+            let inner = self.{field}.take()?;
+            let (inner, u) = ::rent_to_own::RentToOwn::with(inner, f);
+            self.{field} = inner;
+            ::std::option::Option::Some(u)
+        }}",
+        doc = doc,
+        vis = field.vis,
+        method = method,
+        ty = field.ty,
+        field = field.name,
+    )
+}
+
+fn stringify(tokens: Vec<TokenTree>) -> String {
+    tokens.into_iter().collect::<TokenStream>().to_string()
+}
+
+fn is_punct(tt: &TokenTree, ch: char) -> bool {
+    match *tt {
+        TokenTree::Punct(ref p) => p.as_char() == ch,
+        _ => false,
+    }
+}
+
+/// Skip over any `#[...]` attributes.
+fn skip_attributes<I: Iterator<Item = TokenTree>>(tokens: &mut Peekable<I>) {
+    while tokens.peek().is_some_and(|tt| is_punct(tt, '#')) {
+        tokens.next();
+        tokens.next();
+    }
+}
+
+/// Parse a `pub` or `pub(...)` visibility, if any.
+fn parse_visibility<I: Iterator<Item = TokenTree>>(tokens: &mut Peekable<I>) -> String {
+    match tokens.peek() {
+        Some(TokenTree::Ident(i)) if i.to_string() == "pub" => {}
+        _ => return String::new(),
+    }
+    let mut vis = vec![tokens.next().unwrap()];
+    if let Some(TokenTree::Group(g)) = tokens.peek() {
+        if g.delimiter() == Delimiter::Parenthesis {
+            vis.push(tokens.next().unwrap());
+        }
+    }
+    stringify(vis)
+}
+
+/// Tracks how deeply nested inside angle brackets a sequence of tokens is.
+/// Other brackets are already nested inside `TokenTree::Group`s.
+struct AngleDepth {
+    depth: i32,
+    after_dash: bool,
+}
+
+impl AngleDepth {
+    fn new(depth: i32) -> AngleDepth {
+        AngleDepth {
+            depth,
+            after_dash: false,
+        }
+    }
+
+    /// Account for the next token, and return the depth just after it.
+    fn step(&mut self, tt: &TokenTree) -> i32 {
+        let mut is_dash = false;
+        if let TokenTree::Punct(ref p) = *tt {
+            match p.as_char() {
+                '<' => self.depth += 1,
+                // Don't mistake the `>` in `->` for a closing angle bracket.
+                '>' if !self.after_dash => self.depth -= 1,
+                '-' => is_dash = p.spacing() == Spacing::Joint,
+                _ => {}
+            }
+        }
+        self.after_dash = is_dash;
+        self.depth
+    }
+}
+
+/// Split `tokens` at commas that are not nested inside angle brackets.
+fn split_top_level_commas(tokens: Vec<TokenTree>) -> Vec<Vec<TokenTree>> {
+    let mut chunks = vec![vec![]];
+    let mut depth = AngleDepth::new(0);
+    for tt in tokens {
+        if depth.step(&tt) == 0 && is_punct(&tt, ',') {
+            chunks.push(vec![]);
+            continue;
+        }
+        chunks.last_mut().unwrap().push(tt);
+    }
+    chunks.retain(|chunk| !chunk.is_empty());
+    chunks
+}
+
+/// Parse the generics after the opening `<`, up to and including the matching
+/// closing `>`.
+fn parse_generics<I: Iterator<Item = TokenTree>>(
+    tokens: &mut Peekable<I>,
+) -> Result<Generics, String> {
+    let mut inner = vec![];
+    let mut depth = AngleDepth::new(1);
+    loop {
+        let tt = tokens.next().ok_or("unterminated generics")?;
+        if depth.step(&tt) == 0 {
+            break;
+        }
+        inner.push(tt);
+    }
+
+    let mut params = vec![];
+    let mut args = vec![];
+    for param in split_top_level_commas(inner) {
+        let mut param = param.into_iter().peekable();
+        skip_attributes(&mut param);
+        let param: Vec<_> = param.collect();
+
+        let arg = match (&param[0], param.get(1)) {
+            (TokenTree::Punct(p), Some(lifetime)) if p.as_char() == '\'' => {
+                format!("'{}", lifetime)
+            }
+            (TokenTree::Ident(i), Some(name)) if i.to_string() == "const" => name.to_string(),
+            (name, _) => name.to_string(),
+        };
+        args.push(arg);
+
+        // Defaults aren't allowed on `impl` generics. Only an `=` outside of
+        // any angle brackets starts one: others are associated type bindings,
+        // like `I: Iterator<Item = u8>`.
+        let mut depth = AngleDepth::new(0);
+        let without_default = param
+            .into_iter()
+            .take_while(|tt| !(depth.step(tt) == 0 && is_punct(tt, '=')));
+        params.push(stringify(without_default.collect()));
+    }
+
+    Ok(Generics {
+        params: params.join(", "),
+        args: args.join(", "),
+    })
+}
+
+/// Parse the named fields of a struct, keeping only those of type `Option<T>`.
+fn parse_option_fields(body: TokenStream) -> Result<Vec<OptionField>, String> {
+    let mut fields = vec![];
+    for field in split_top_level_commas(body.into_iter().collect()) {
+        let mut field = field.into_iter().peekable();
+        skip_attributes(&mut field);
+        let vis = parse_visibility(&mut field);
+        let name = match field.next() {
+            Some(TokenTree::Ident(i)) => i.to_string(),
+            _ => return Err("expected a field name".into()),
+        };
+        match field.next() {
+            Some(ref tt) if is_punct(tt, ':') => {}
+            _ => return Err(format!("expected a type for field `{}`", name)),
+        }
+        if let Some(ty) = option_inner_type(field.collect()) {
+            fields.push(OptionField { vis, name, ty });
+        }
+    }
+    Ok(fields)
+}
+
+/// If `ty` is `Option<T>`, or a path to `Option` like `std::option::Option<T>`,
+/// then get `T`.
+fn option_inner_type(ty: Vec<TokenTree>) -> Option<String> {
+    let open = ty.iter().position(|tt| is_punct(tt, '<'))?;
+    let path: String = ty[..open].iter().map(|tt| tt.to_string()).collect();
+    match &path[..] {
+        "Option"
+        | "std::option::Option"
+        | "::std::option::Option"
+        | "core::option::Option"
+        | "::core::option::Option" => {}
+        _ => return None,
+    }
+    if !is_punct(ty.last()?, '>') {
+        return None;
+    }
+    Some(stringify(ty[open + 1..ty.len() - 1].to_vec()))
+}
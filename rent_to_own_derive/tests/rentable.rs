@@ -0,0 +1,94 @@
+use rent_to_own::RentToOwn;
+use rent_to_own_derive::Rentable;
+
+#[derive(Rentable)]
+struct Plain {
+    a: Option<u32>,
+    pub b: ::std::option::Option<Vec<u32>>,
+    #[allow(dead_code)]
+    not_rentable: u32,
+}
+
+#[derive(Rentable)]
+struct Generic<'a, T: Clone, const N: usize = 3>
+where
+    T: Default,
+{
+    r#ref: Option<&'a [T; N]>,
+    f: Option<Box<dyn Fn(T) -> T>>,
+}
+
+#[derive(Rentable)]
+struct Iter<I: Iterator<Item = u8> = std::vec::IntoIter<u8>> {
+    it: Option<I>,
+}
+
+fn take_if<'a, T>(x: &'a mut RentToOwn<'a, T>, take: bool) -> Option<T> {
+    if take {
+        Some(x.take())
+    } else {
+        None
+    }
+}
+
+#[test]
+fn untaken_field_is_put_back() {
+    let mut p = Plain {
+        a: Some(5),
+        b: None,
+        not_rentable: 0,
+    };
+    assert_eq!(p.rent_a(|a| take_if(a, false)), Some(None));
+    assert_eq!(p.a, Some(5));
+}
+
+#[test]
+fn taken_field_is_left_empty() {
+    let mut p = Plain {
+        a: None,
+        b: Some(vec![1, 2]),
+        not_rentable: 0,
+    };
+    assert_eq!(p.rent_b(|b| take_if(b, true)), Some(Some(vec![1, 2])));
+    assert_eq!(p.b, None);
+}
+
+#[test]
+fn empty_field_is_not_rented() {
+    let mut p = Plain {
+        a: None,
+        b: None,
+        not_rentable: 0,
+    };
+    assert_eq!(p.rent_a(|_| unreachable!()), None::<()>);
+}
+
+#[test]
+fn generic_struct() {
+    let arr = [1, 2, 3];
+    let mut g: Generic<u32> = Generic {
+        r#ref: Some(&arr),
+        f: Some(Box::new(|x| x + 1)),
+    };
+    assert_eq!(g.rent_ref(|r| r[1]), Some(2));
+    assert_eq!(g.rent_f(|f| f(1)), Some(2));
+    assert!(g.rent_f(|f| take_if(f, true)).is_some());
+    assert!(g.f.is_none());
+    assert!(g.r#ref.is_some());
+}
+
+#[test]
+fn associated_type_binding_in_bounds() {
+    let mut i: Iter = Iter {
+        it: Some(vec![1, 2, 3].into_iter()),
+    };
+    assert_eq!(i.rent_it(|it| it.next()), Some(Some(1)));
+    assert_eq!(
+        i.rent_it(|it| take_if(it, true))
+            .unwrap()
+            .unwrap()
+            .sum::<u8>(),
+        5
+    );
+    assert!(i.it.is_none());
+}
@@ -0,0 +1,198 @@
+//! `AsyncRentToOwn<T>`: conditionally taking ownership across `.await` points.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+
+use super::{RentToOwn, Slot};
+
+/// A wrapper around a `T` that allows an `async` function to conditionally
+/// take ownership of the inner `T` value, or simply use it like a `&mut T`
+/// reference, even across `.await` points.
+///
+/// Unlike `RentToOwn<T>`, which is handed out as `&'a mut RentToOwn<'a, T>`
+/// and relies on lifetime trickery to prevent use-after-take, an
+/// `AsyncRentToOwn<T>` is handed out by value, and `take` consumes it. This
+/// way use-after-take is statically prevented by the usual move semantics, and
+/// the resulting futures are `Send` whenever `T` is, which is not the case for
+/// the `&'a mut RentToOwn<'a, T>` lifetime trick.
+///
+/// Construct one with `RentToOwn::with_async`.
+pub struct AsyncRentToOwn<'a, T: 'a> {
+    // Invariant: whenever an `AsyncRentToOwn` exists, its slot is full. `take`
+    // consuming `self` upholds this.
+    inner: &'a mut Slot<T>,
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for AsyncRentToOwn<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsyncRentToOwn")
+            .field("inner", &**self)
+            .finish()
+    }
+}
+
+impl<'a, T: Hash> Hash for AsyncRentToOwn<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<'a, T> Deref for AsyncRentToOwn<'a, T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.inner.get() }
+    }
+}
+
+impl<'a, T> DerefMut for AsyncRentToOwn<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.inner.get_mut() }
+    }
+}
+
+impl<'a, T> AsyncRentToOwn<'a, T> {
+    /// Take ownership of the inner `T` value.
+    ///
+    /// This consumes the `AsyncRentToOwn`, so it cannot be used again
+    /// afterwards.
+    pub fn take(self) -> T {
+        unsafe { self.inner.take() }
+    }
+}
+
+impl<'a, T: 'a> RentToOwn<'a, T> {
+    /// Give the asynchronous function `f` the option to take ownership of
+    /// `inner`.
+    ///
+    /// This is the `async` version of `RentToOwn::with`: it creates an
+    /// `AsyncRentToOwn` from the given `inner` value and then invokes and
+    /// awaits the function `f` with it.
+    ///
+    /// The resulting future's output is a pair of:
+    ///
+    /// 1. If the function took ownership of the inner value, `None`, otherwise
+    ///    `Some(inner)`.
+    ///
+    /// 2. The value returned by the function.
+    ///
+    /// ```
+    /// use rent_to_own::{AsyncRentToOwn, RentToOwn};
+    /// # use std::future::Future;
+    /// # fn block_on<F: Future>(f: F) -> F::Output {
+    /// #     let mut f = Box::pin(f);
+    /// #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(x) = f.as_mut().poll(&mut cx) {
+    /// #             return x;
+    /// #         }
+    /// #     }
+    /// # }
+    ///
+    /// struct Connection;
+    ///
+    /// async fn handshake(_conn: &mut Connection) -> bool {
+    ///     // ...
+    /// #   true
+    /// }
+    ///
+    /// async fn maybe_claim(mut conn: AsyncRentToOwn<'_, Connection>) -> Option<Connection> {
+    ///     if handshake(&mut conn).await {
+    ///         Some(conn.take())
+    ///     } else {
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// # block_on(async {
+    /// let (orig, claimed) = RentToOwn::with_async(Connection, maybe_claim).await;
+    /// assert!(orig.is_none());
+    /// assert!(claimed.is_some());
+    /// # });
+    /// ```
+    pub async fn with_async<F, U>(inner: T, f: F) -> (Option<T>, U)
+    where
+        F: for<'b> AsyncFnOnce(AsyncRentToOwn<'b, T>) -> U,
+    {
+        let mut inner = Slot::new(inner);
+        let u = {
+            let me = AsyncRentToOwn { inner: &mut inner };
+            f(me).await
+        };
+        (inner.into_inner(), u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RentToOwn;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    fn block_on<F: Future>(f: F) -> F::Output {
+        let mut f = Box::pin(f);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(x) = f.as_mut().poll(&mut cx) {
+                return x;
+            }
+        }
+    }
+
+    /// A future that is pending the first time it is polled.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    fn is_send<T: Send>(t: T) -> T {
+        t
+    }
+
+    #[test]
+    fn with_async_gives_back_untaken_ownership() {
+        let (orig, x) = block_on(RentToOwn::with_async(5, async |mut x| {
+            YieldOnce(false).await;
+            *x += 1;
+            YieldOnce(false).await;
+            *x
+        }));
+        assert_eq!(orig, Some(6));
+        assert_eq!(x, 6);
+    }
+
+    #[test]
+    fn with_async_does_not_give_back_taken_ownership() {
+        let (orig, x) = block_on(RentToOwn::with_async(5, async |x| {
+            YieldOnce(false).await;
+            x.take()
+        }));
+        assert!(orig.is_none());
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn with_async_future_is_send() {
+        let (orig, _) = block_on(is_send(RentToOwn::with_async(
+            String::from("hi"),
+            async |x| {
+                YieldOnce(false).await;
+                x.take()
+            },
+        )));
+        assert!(orig.is_none());
+    }
+}
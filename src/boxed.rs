@@ -0,0 +1,150 @@
+//! `BoxedRentToOwn<T>`: conditionally taking ownership of boxed values.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use super::{RentToOwn, Slot};
+
+/// A wrapper around a boxed `T` that allows users to conditionally take
+/// ownership of the inner `T` value, or simply use it like a `&mut T`
+/// reference.
+///
+/// `RentToOwn::with` moves its inner value onto the stack, and `take` moves it
+/// out again, which is a lot of copying for large values. `BoxedRentToOwn<T>`
+/// keeps the inner value on the heap instead, so that giving it out and taking
+/// ownership of it only ever moves the `Box<T>` pointer.
+///
+/// Construct one with `RentToOwn::with_boxed`.
+pub struct BoxedRentToOwn<'a, T: 'a> {
+    // Invariant: whenever a `BoxedRentToOwn` is accessible, its slot is full.
+    // The lifetime on `take`'s and `take_boxed`'s `self` reference upholds
+    // this.
+    inner: &'a mut Slot<Box<T>>,
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for BoxedRentToOwn<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoxedRentToOwn")
+            .field("inner", &**self)
+            .finish()
+    }
+}
+
+impl<'a, T> Deref for BoxedRentToOwn<'a, T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.inner.get() }
+    }
+}
+
+impl<'a, T> DerefMut for BoxedRentToOwn<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.inner.get_mut() }
+    }
+}
+
+impl<'a, T> BoxedRentToOwn<'a, T> {
+    /// Take ownership of the inner value, still in its box.
+    ///
+    /// Like `RentToOwn::take`, the lifetime on the `self` reference statically
+    /// disallows use-after-take.
+    pub fn take_boxed(&'a mut self) -> Box<T> {
+        unsafe { self.inner.take() }
+    }
+
+    /// Take ownership of the inner `T` value, moving it out of its box.
+    ///
+    /// Prefer `take_boxed` to avoid moving large values.
+    pub fn take(&'a mut self) -> T {
+        *self.take_boxed()
+    }
+}
+
+impl<'a, T: 'a> RentToOwn<'a, T> {
+    /// Give the function `f` the option to take ownership of the boxed `inner`
+    /// value.
+    ///
+    /// This is the boxed version of `RentToOwn::with`: it creates a
+    /// `BoxedRentToOwn` from the given `inner` value and then invokes the
+    /// function `f` with it.
+    ///
+    /// The return value is a pair of:
+    ///
+    /// 1. If the closure took ownership of the inner value, `None`, otherwise
+    ///    `Some(inner)`, still boxed.
+    ///
+    /// 2. The value returned by the closure.
+    ///
+    /// ```
+    /// use rent_to_own::{BoxedRentToOwn, RentToOwn};
+    ///
+    /// struct Frame {
+    ///     pixels: [u8; 1 << 16],
+    ///     dirty: bool,
+    /// }
+    ///
+    /// // Only take ownership of the frame if it needs to be sent off.
+    /// fn maybe_send<'a>(frame: &'a mut BoxedRentToOwn<'a, Frame>) -> Option<Box<Frame>> {
+    ///     if frame.dirty {
+    ///         Some(frame.take_boxed())
+    ///     } else {
+    ///         frame.pixels[0] = 1;
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// let frame = Box::new(Frame { pixels: [0; 1 << 16], dirty: false });
+    /// let (frame, sent) = RentToOwn::with_boxed(frame, maybe_send);
+    /// assert!(sent.is_none());
+    ///
+    /// let mut frame = frame.unwrap();
+    /// frame.dirty = true;
+    /// let (orig, sent) = RentToOwn::with_boxed(frame, maybe_send);
+    /// assert!(orig.is_none());
+    /// assert_eq!(sent.unwrap().pixels[0], 1);
+    /// ```
+    pub fn with_boxed<F, U>(inner: Box<T>, f: F) -> (Option<Box<T>>, U)
+    where
+        F: for<'b> FnOnce(&'b mut BoxedRentToOwn<'b, T>) -> U,
+    {
+        let mut inner = Slot::new(inner);
+        let u = {
+            let mut me = BoxedRentToOwn { inner: &mut inner };
+            f(&mut me)
+        };
+        (inner.into_inner(), u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RentToOwn;
+
+    #[test]
+    fn with_boxed_gives_back_untaken_ownership() {
+        let (orig, x) = RentToOwn::with_boxed(Box::new(5), |x| {
+            **x += 1;
+            **x
+        });
+        assert_eq!(orig, Some(Box::new(6)));
+        assert_eq!(x, 6);
+    }
+
+    #[test]
+    fn with_boxed_does_not_give_back_taken_ownership() {
+        let (orig, x) = RentToOwn::with_boxed(Box::new(5), |x| x.take());
+        assert!(orig.is_none());
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn take_boxed_does_not_move_the_value() {
+        let boxed = Box::new([0u8; 1024]);
+        let addr = &*boxed as *const [u8; 1024];
+        let (orig, taken) = RentToOwn::with_boxed(boxed, |x| x.take_boxed());
+        assert!(orig.is_none());
+        assert_eq!(&*taken as *const [u8; 1024], addr);
+    }
+}
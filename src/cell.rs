@@ -0,0 +1,128 @@
+//! `RentToOwnCell<T>`: conditionally taking ownership through a shared
+//! reference.
+
+use std::cell::RefCell;
+
+/// A cell holding a `T` that allows users to conditionally take ownership of
+/// the inner `T` value, or simply use it, through a shared `&` reference.
+///
+/// This is useful in callback-based code, where only a `&RentToOwnCell<T>` is
+/// available, so the lifetime trickery used by `RentToOwn<T>` is out of the
+/// question. Instead, use-after-take is checked at runtime: `with` and
+/// `try_take` return `None` once the value has been taken.
+///
+/// ```
+/// use rent_to_own::RentToOwnCell;
+///
+/// let cell = RentToOwnCell::new(String::from("resource"));
+///
+/// let on_event = |claim: bool| {
+///     if claim {
+///         cell.try_take()
+///     } else {
+///         cell.with(|r| r.push('!'));
+///         None
+///     }
+/// };
+///
+/// assert_eq!(on_event(false), None);
+/// assert_eq!(on_event(true), Some(String::from("resource!")));
+/// assert!(cell.is_taken());
+/// assert_eq!(on_event(true), None);
+/// ```
+#[derive(Debug)]
+pub struct RentToOwnCell<T> {
+    inner: RefCell<Option<T>>,
+}
+
+impl<T: Default> Default for RentToOwnCell<T> {
+    fn default() -> RentToOwnCell<T> {
+        RentToOwnCell::new(T::default())
+    }
+}
+
+impl<T> RentToOwnCell<T> {
+    /// Construct a new `RentToOwnCell` holding the given value.
+    pub fn new(value: T) -> RentToOwnCell<T> {
+        RentToOwnCell {
+            inner: RefCell::new(Some(value)),
+        }
+    }
+
+    /// Call `f` with a mutable reference to the inner value, or return `None`
+    /// if it has been taken.
+    ///
+    /// Panics if called again, or if `try_take` is called, from within `f`.
+    pub fn with<F, U>(&self, f: F) -> Option<U>
+    where
+        F: FnOnce(&mut T) -> U,
+    {
+        self.inner.borrow_mut().as_mut().map(f)
+    }
+
+    /// Take ownership of the inner value, or return `None` if it has already
+    /// been taken.
+    ///
+    /// Panics if called from within `with`'s closure.
+    pub fn try_take(&self) -> Option<T> {
+        self.inner.borrow_mut().take()
+    }
+
+    /// Returns `true` if the inner value has been taken.
+    pub fn is_taken(&self) -> bool {
+        match self.inner.try_borrow() {
+            Ok(inner) => inner.is_none(),
+            // The value is currently being used by `with`'s closure.
+            Err(_) => false,
+        }
+    }
+
+    /// Consume the cell, and get the inner value back, or `None` if it has
+    /// been taken.
+    pub fn into_inner(self) -> Option<T> {
+        self.inner.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RentToOwnCell;
+
+    #[test]
+    fn with_uses_untaken_value() {
+        let cell = RentToOwnCell::new(5);
+        assert_eq!(cell.with(|x| *x += 1), Some(()));
+        assert!(!cell.is_taken());
+        assert_eq!(cell.into_inner(), Some(6));
+    }
+
+    #[test]
+    fn try_take_only_once() {
+        let cell = RentToOwnCell::new(5);
+        assert_eq!(cell.try_take(), Some(5));
+        assert!(cell.is_taken());
+        assert_eq!(cell.try_take(), None);
+        assert_eq!(cell.with(|x| *x), None);
+        assert_eq!(cell.into_inner(), None);
+    }
+
+    #[test]
+    fn default_is_not_taken() {
+        let cell = RentToOwnCell::<u32>::default();
+        assert!(!cell.is_taken());
+        assert_eq!(cell.try_take(), Some(0));
+    }
+
+    #[test]
+    fn is_taken_within_with() {
+        let cell = RentToOwnCell::new(5);
+        cell.with(|_| assert!(!cell.is_taken()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn try_take_within_with_panics() {
+        let cell = RentToOwnCell::new(5);
+        cell.with(|_| cell.try_take());
+    }
+}
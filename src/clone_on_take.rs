@@ -0,0 +1,261 @@
+//! Cloning instead of taking, when the caller still needs the value.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::{RentToOwn, Slot};
+
+/// What happened to the value given to `RentToOwn::with_clone_on_take`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CloneOnTake<T> {
+    /// The closure neither took nor cloned the value. Here it is.
+    Kept(T),
+    /// The closure used `take_or_clone`, and got a clone of the value. Here is
+    /// the original.
+    Cloned(T),
+    /// The closure took ownership of the value, with `take` or one of its
+    /// variants.
+    Taken,
+}
+
+/// A `RentToOwn` given out by `RentToOwn::with_clone_on_take`, which also
+/// records whether its value was cloned with `take_or_clone`.
+///
+/// Like `RentToOwn<T>`, it dereferences to its inner `T` value, and ownership
+/// of that value can be taken with `take`.
+pub struct CloneTrackingRentToOwn<'a, T: 'a> {
+    inner: RentToOwn<'a, T>,
+    cloned: &'a mut bool,
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for CloneTrackingRentToOwn<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CloneTrackingRentToOwn")
+            .field("inner", &**self)
+            .field("cloned", &*self.cloned)
+            .finish()
+    }
+}
+
+impl<'a, T> Deref for CloneTrackingRentToOwn<'a, T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<'a, T> DerefMut for CloneTrackingRentToOwn<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<'a, T> CloneTrackingRentToOwn<'a, T> {
+    /// Take ownership of the inner `T` value.
+    ///
+    /// Like `RentToOwn::take`, the lifetime on the `self` reference statically
+    /// disallows use-after-take.
+    pub fn take(&'a mut self) -> T {
+        self.inner.take()
+    }
+}
+
+impl<'a, T: Clone> CloneTrackingRentToOwn<'a, T> {
+    /// Get an owned clone of the inner `T` value, leaving the original in
+    /// place, and report the clone to `with_clone_on_take`'s caller.
+    pub fn take_or_clone(&mut self) -> T {
+        *self.cloned = true;
+        self.inner.take_or_clone()
+    }
+}
+
+impl<'a, T: 'a + Clone> RentToOwn<'a, T> {
+    /// Give the function `f` the option to take ownership of `inner`, and
+    /// report whether it took a clone of it with `take_or_clone`.
+    ///
+    /// This is like `RentToOwn::with`, except that `f` is given a
+    /// `CloneTrackingRentToOwn`, and the returned `CloneOnTake` distinguishes
+    /// a value that `f` merely used from one that `f` cloned.
+    ///
+    /// The return value is a pair of:
+    ///
+    /// 1. A `CloneOnTake` describing whether the value was kept, cloned, or
+    ///    taken, along with the original value if it wasn't taken.
+    ///
+    /// 2. The value returned by the closure.
+    ///
+    /// ```
+    /// use rent_to_own::{CloneOnTake, CloneTrackingRentToOwn, RentToOwn};
+    ///
+    /// fn store(name: &mut CloneTrackingRentToOwn<String>, names: &mut Vec<String>) {
+    ///     if name.starts_with('b') {
+    ///         names.push(name.take_or_clone());
+    ///     }
+    /// }
+    ///
+    /// let mut names = vec![];
+    ///
+    /// let (orig, _) = RentToOwn::with_clone_on_take(String::from("alice"), |n| store(n, &mut names));
+    /// assert_eq!(orig, CloneOnTake::Kept(String::from("alice")));
+    ///
+    /// let (orig, _) = RentToOwn::with_clone_on_take(String::from("bob"), |n| store(n, &mut names));
+    /// assert_eq!(orig, CloneOnTake::Cloned(String::from("bob")));
+    ///
+    /// assert_eq!(names, ["bob"]);
+    /// ```
+    pub fn with_clone_on_take<F, U>(inner: T, f: F) -> (CloneOnTake<T>, U)
+    where
+        F: for<'b> FnOnce(&'b mut CloneTrackingRentToOwn<'b, T>) -> U,
+    {
+        let mut inner = Slot::new(inner);
+        let mut cloned = false;
+        let u = {
+            let mut me = CloneTrackingRentToOwn {
+                inner: RentToOwn { inner: &mut inner },
+                cloned: &mut cloned,
+            };
+            f(&mut me)
+        };
+        let outcome = match inner.into_inner() {
+            Some(t) if cloned => CloneOnTake::Cloned(t),
+            Some(t) => CloneOnTake::Kept(t),
+            None => CloneOnTake::Taken,
+        };
+        (outcome, u)
+    }
+}
+
+impl<'a, T: Clone> RentToOwn<'a, T> {
+    /// Get an owned clone of the inner `T` value, leaving the original in
+    /// place.
+    ///
+    /// Unlike `take`, this does not consume the `RentToOwn`, so it can keep
+    /// being used afterwards.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    ///
+    /// let (orig, copy) = RentToOwn::with(vec![1, 2, 3], |v| {
+    ///     let copy = v.take_or_clone();
+    ///     v.push(4);
+    ///     copy
+    /// });
+    ///
+    /// assert_eq!(orig, Some(vec![1, 2, 3, 4]));
+    /// assert_eq!(copy, [1, 2, 3]);
+    /// ```
+    pub fn take_or_clone(&mut self) -> T {
+        (**self).clone()
+    }
+}
+
+impl<'a, T> RentToOwn<'a, Rc<T>> {
+    /// Take ownership of the value inside the inner `Rc<T>`, if the `Rc` is
+    /// the only reference to it. Otherwise, give this `RentToOwn` back so that
+    /// it can keep being used.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    /// use std::rc::Rc;
+    ///
+    /// let shared = Rc::new(5);
+    /// let (orig, _) = RentToOwn::with(shared.clone(), |rc| {
+    ///     assert!(rc.take_if_sole().is_err());
+    /// });
+    /// assert!(orig.is_some());
+    ///
+    /// drop(orig);
+    /// let (orig, x) = RentToOwn::with(shared, |rc| rc.take_if_sole().ok());
+    /// assert!(orig.is_none());
+    /// assert_eq!(x, Some(5));
+    /// ```
+    pub fn take_if_sole(&'a mut self) -> Result<T, &'a mut RentToOwn<'a, Rc<T>>> {
+        match Rc::try_unwrap(unsafe { self.inner.take() }) {
+            Ok(t) => Ok(t),
+            Err(rc) => {
+                self.inner.put(rc);
+                Err(self)
+            }
+        }
+    }
+}
+
+impl<'a, T> RentToOwn<'a, Arc<T>> {
+    /// Take ownership of the value inside the inner `Arc<T>`, if the `Arc` is
+    /// the only reference to it. Otherwise, give this `RentToOwn` back so that
+    /// it can keep being used.
+    ///
+    /// See also the `Rc<T>` version of `take_if_sole`.
+    pub fn take_if_sole(&'a mut self) -> Result<T, &'a mut RentToOwn<'a, Arc<T>>> {
+        match Arc::try_unwrap(unsafe { self.inner.take() }) {
+            Ok(t) => Ok(t),
+            Err(arc) => {
+                self.inner.put(arc);
+                Err(self)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CloneOnTake;
+    use crate::RentToOwn;
+    use std::sync::Arc;
+
+    #[test]
+    fn take_or_clone_leaves_value_in_with() {
+        let (orig, x) = RentToOwn::with(5, |x| {
+            let y = x.take_or_clone();
+            **x += 1;
+            y
+        });
+        assert_eq!(orig, Some(6));
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn take_or_clone_clones_in_with_clone_on_take() {
+        let (orig, x) = RentToOwn::with_clone_on_take(5, |x| x.take_or_clone());
+        assert_eq!(orig, CloneOnTake::Cloned(5));
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn with_clone_on_take_reports_kept_and_taken() {
+        let (orig, _) = RentToOwn::with_clone_on_take(5, |x| **x += 1);
+        assert_eq!(orig, CloneOnTake::Kept(6));
+
+        let (orig, x) = RentToOwn::with_clone_on_take(5, |x| x.take());
+        assert_eq!(orig, CloneOnTake::Taken);
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn with_clone_on_take_reports_taken_after_clone() {
+        let (orig, x) = RentToOwn::with_clone_on_take(5, |x| {
+            x.take_or_clone();
+            x.take()
+        });
+        assert_eq!(orig, CloneOnTake::Taken);
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn arc_take_if_sole() {
+        let arc = Arc::new(5);
+        let other = arc.clone();
+        let (orig, _) = RentToOwn::with(arc, |arc| {
+            let arc = arc.take_if_sole().unwrap_err();
+            assert_eq!(**arc, Arc::new(5));
+        });
+        drop(other);
+        let (orig, x) = RentToOwn::with(orig.unwrap(), |arc| arc.take_if_sole().ok());
+        assert!(orig.is_none());
+        assert_eq!(x, Some(5));
+    }
+}
@@ -0,0 +1,169 @@
+//! `HotSwap<T>`: atomically replace a shared value while readers keep their
+//! snapshots, and conditionally claim ownership of the outgoing value.
+
+use std::mem;
+use std::ops::Deref;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// A shared slot holding a `T` value that can be atomically replaced.
+///
+/// Readers call `load` to get a cheap `Arc<T>` snapshot of the current
+/// value. A writer calls `swap` to install a new value; readers that already
+/// hold a snapshot keep seeing the old value, and every subsequent `load` sees
+/// the new one. The outgoing value is returned as a `Retired<T>`, from which
+/// the writer can take ownership once all the readers' snapshots have been
+/// dropped.
+///
+/// ```
+/// use rent_to_own::HotSwap;
+///
+/// let config = HotSwap::new(String::from("v1"));
+///
+/// let snapshot = config.load();
+/// let retired = config.swap(String::from("v2"));
+///
+/// // Existing readers keep the old value, new readers see the new one.
+/// assert_eq!(*snapshot, "v1");
+/// assert_eq!(*config.load(), "v2");
+///
+/// // We can't claim the old value while a reader still has it...
+/// let retired = retired.try_take().unwrap_err();
+///
+/// // ...but we can once that reader is done with it.
+/// drop(snapshot);
+/// assert_eq!(retired.try_take().unwrap(), "v1");
+/// ```
+#[derive(Debug)]
+pub struct HotSwap<T> {
+    current: RwLock<Arc<T>>,
+}
+
+impl<T> HotSwap<T> {
+    /// Construct a new `HotSwap` holding the given initial value.
+    pub fn new(value: T) -> HotSwap<T> {
+        HotSwap {
+            current: RwLock::new(Arc::new(value)),
+        }
+    }
+
+    /// Get a snapshot of the current value.
+    ///
+    /// The snapshot stays valid, and keeps pointing at the same value, even if
+    /// the value is replaced with `swap` afterwards.
+    pub fn load(&self) -> Arc<T> {
+        // Cloning or replacing an `Arc` can't leave it in an inconsistent
+        // state, so it is fine to ignore poisoning.
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Atomically install `value` as the new current value, and return the
+    /// outgoing value.
+    pub fn swap(&self, value: T) -> Retired<T> {
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        let old = mem::replace(&mut *current, Arc::new(value));
+        Retired { value: old }
+    }
+}
+
+/// A value that was replaced in a `HotSwap`, but may still be in use by
+/// readers that took a snapshot of it before it was replaced.
+///
+/// `Retired<T>` dereferences to the outgoing `T` value, and ownership of it
+/// can be taken with `try_take` once there are no remaining readers.
+#[derive(Debug)]
+pub struct Retired<T> {
+    value: Arc<T>,
+}
+
+impl<T> Deref for Retired<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Retired<T> {
+    /// Get the number of readers that still hold a snapshot of the outgoing
+    /// value.
+    pub fn readers(&self) -> usize {
+        Arc::strong_count(&self.value) - 1
+    }
+
+    /// Take ownership of the outgoing value if there are no remaining readers,
+    /// otherwise give back the `Retired<T>` so that it can be tried again
+    /// later.
+    pub fn try_take(self) -> Result<T, Retired<T>> {
+        Arc::try_unwrap(self.value).map_err(|value| Retired { value })
+    }
+
+    /// Give up on taking ownership of the outgoing value, and get it as a
+    /// plain shared `Arc<T>` instead.
+    pub fn into_arc(self) -> Arc<T> {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HotSwap;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn load_sees_latest_swap() {
+        let slot = HotSwap::new(1);
+        assert_eq!(*slot.load(), 1);
+        slot.swap(2);
+        assert_eq!(*slot.load(), 2);
+    }
+
+    #[test]
+    fn snapshots_keep_the_old_value() {
+        let slot = HotSwap::new(1);
+        let snapshot = slot.load();
+        let retired = slot.swap(2);
+        assert_eq!(*snapshot, 1);
+        assert_eq!(*retired, 1);
+        assert_eq!(retired.readers(), 1);
+    }
+
+    #[test]
+    fn try_take_waits_for_readers_to_drain() {
+        let slot = HotSwap::new(String::from("old"));
+        let snapshot = slot.load();
+        let retired = slot.swap(String::from("new"));
+
+        let retired = retired.try_take().unwrap_err();
+        drop(snapshot);
+        assert_eq!(retired.readers(), 0);
+        assert_eq!(retired.try_take().unwrap(), "old");
+    }
+
+    #[test]
+    fn readers_on_other_threads() {
+        let slot = Arc::new(HotSwap::new(0));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let slot = slot.clone();
+                thread::spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..100 {
+                        let now = *slot.load();
+                        assert!(now >= last);
+                        last = now;
+                    }
+                })
+            })
+            .collect();
+        for i in 1..100 {
+            slot.swap(i);
+        }
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert_eq!(*slot.load(), 99);
+    }
+}
@@ -0,0 +1,150 @@
+//! `Lease<T>`: temporarily taking ownership of a `RentToOwn`'s inner value.
+
+use std::fmt;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+
+use super::RentToOwn;
+
+/// A guard that owns a `RentToOwn`'s inner value, and puts it back into the
+/// `RentToOwn` when dropped, unless it is permanently claimed with `keep`.
+///
+/// Construct one with `RentToOwn::lease`.
+pub struct Lease<'a, T: 'a> {
+    value: ManuallyDrop<T>,
+    // Invariant: the owner's slot is empty for as long as the lease exists.
+    owner: &'a mut RentToOwn<'a, T>,
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Lease<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lease")
+            .field("value", &*self.value)
+            .finish()
+    }
+}
+
+impl<'a, T> Deref for Lease<'a, T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> DerefMut for Lease<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<'a, T> Drop for Lease<'a, T> {
+    fn drop(&mut self) {
+        // Safety: `self.value` is never used again.
+        let value = unsafe { ManuallyDrop::take(&mut self.value) };
+        self.owner.inner.put(value);
+    }
+}
+
+impl<'a, T> Lease<'a, T> {
+    /// Permanently claim ownership of the leased value, so that it is not put
+    /// back into the `RentToOwn`.
+    pub fn keep(self) -> T {
+        let mut me = ManuallyDrop::new(self);
+        // Safety: `me` is never dropped, so `me.value` is never used again.
+        unsafe { ManuallyDrop::take(&mut me.value) }
+    }
+
+    /// Put the leased value back into the `RentToOwn` right away, and get the
+    /// `RentToOwn` back so that it can keep being used.
+    pub fn give_back(self) -> &'a mut RentToOwn<'a, T> {
+        let mut me = ManuallyDrop::new(self);
+        // Safety: `me` is never dropped, so neither `me.value` nor `me.owner`
+        // is used again.
+        let value = unsafe { ManuallyDrop::take(&mut me.value) };
+        let owner = unsafe { ptr::read(&me.owner) };
+        owner.inner.put(value);
+        owner
+    }
+}
+
+impl<'a, T> RentToOwn<'a, T> {
+    /// Temporarily take ownership of the inner `T` value.
+    ///
+    /// The returned `Lease` owns the inner value, and puts it back into this
+    /// `RentToOwn` when it is dropped, so that whoever called `RentToOwn::with`
+    /// gets it back. Use `Lease::keep` to permanently take ownership instead,
+    /// or `Lease::give_back` to keep using this `RentToOwn` afterwards.
+    ///
+    /// Like `take`, the lifetime on the `self` reference makes the `RentToOwn`
+    /// unusable while its value is leased out, and for good if the lease is
+    /// kept.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    ///
+    /// fn consume(v: Vec<u32>) -> Vec<u32> {
+    ///     v.into_iter().map(|x| x * 2).collect()
+    /// }
+    ///
+    /// let (orig, _) = RentToOwn::with(vec![1, 2, 3], |v| {
+    ///     let mut lease = v.lease();
+    ///     let doubled = consume(lease.clone());
+    ///     *lease = doubled;
+    ///     // The lease is dropped here, putting the doubled vector back.
+    /// });
+    ///
+    /// assert_eq!(orig, Some(vec![2, 4, 6]));
+    /// ```
+    pub fn lease(&'a mut self) -> Lease<'a, T> {
+        let value = unsafe { self.inner.take() };
+        Lease {
+            value: ManuallyDrop::new(value),
+            owner: self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RentToOwn;
+
+    #[test]
+    fn dropped_lease_gives_back_ownership() {
+        let (orig, _) = RentToOwn::with(5, |x| {
+            let mut lease = x.lease();
+            *lease += 1;
+        });
+        assert_eq!(orig, Some(6));
+    }
+
+    #[test]
+    fn kept_lease_takes_ownership() {
+        let (orig, x) = RentToOwn::with(5, |x| x.lease().keep());
+        assert!(orig.is_none());
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn given_back_lease_can_be_used_again() {
+        let (orig, x) = RentToOwn::with(5, |x| {
+            let x = x.lease().give_back();
+            **x += 1;
+            x.take()
+        });
+        assert!(orig.is_none());
+        assert_eq!(x, 6);
+    }
+
+    #[test]
+    fn lease_gives_back_ownership_on_panic() {
+        let (orig, result) = RentToOwn::with_catch_unwind(5, |x| {
+            let _lease = x.lease();
+            panic!("oh no!")
+        });
+        assert_eq!(orig, Some(5));
+        assert!(result.is_err());
+    }
+}
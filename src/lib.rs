@@ -8,23 +8,29 @@ underlying value.
 `RentToOwn<T>` is useful in situations where
 
 1. a function might want to *conditionally take ownership* of some `T`
-value, and
+   value, and
 
-2. that function cannot take the `T` by value and return an `Option<T>` to maybe
-give the `T` value back if it doesn't want ownership.
+2. that function cannot take the `T` by value and return an `Option<T>` to
+   maybe give the `T` value back if it doesn't want ownership.
 
 `RentToOwn<T>` dereferences (immutably and mutably) to its inner `T` value, and
 additionally provides a `take` method that gives up ownership of the inner value
 to the caller.
 
-Under the covers, `RentToOwn<T>` is essentially an `Option<T>` that gets
-unwrapped when dereferenced and calls `Option::take` if we need to take
-ownership of the inner value. The key advantage over using `Option<T>` directly,
-other than the `Deref` sugar, is some lifetime trickery to statically prevent
-all unwrapping panics that would arise from using the `RentToOwn<T>` wrapper
-again after the inner value has been taken. Once the inner value is taken, the
-borrow checker will ensure that the original `RentToOwn<T>` cannot be used
-anymore. See the `take` method's documentation for details.
+Under the covers, `RentToOwn<T>` is a reference to a slot holding a
+`MaybeUninit<T>` and a flag recording whether the value is still there. Unlike
+an `Option<T>`, the slot is not checked when it is dereferenced: dereferencing
+compiles down to a plain pointer dereference, and `take` simply moves the value
+out and clears the flag. This is sound because of some lifetime trickery that
+statically prevents using the `RentToOwn<T>` wrapper again after the inner value
+has been taken. Once the inner value is taken, the borrow checker will ensure
+that the original `RentToOwn<T>` cannot be used anymore. See the `take` method's
+documentation for details. The flag is only checked once, when
+`RentToOwn::with` returns.
+
+In release builds, `Deref`, `DerefMut` and `take` contain no panic paths at
+all. This is checked at link time by the crate's tests with `cargo test
+--release --features no-panic`.
 
 ## Example
 
@@ -131,27 +137,85 @@ fn use_custom_configuration_or_default(resource: BigExpensiveResource) -> Config
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
 
+use std::any::Any;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
+
+mod async_rent_to_own;
+mod boxed;
+mod cell;
+mod clone_on_take;
+mod hot_swap;
+mod lease;
+mod multi;
+mod outcome;
+mod pinned;
+mod rent_to_own_slice;
+mod renter;
+mod scoped;
+mod shared;
+mod slot;
+mod split;
+mod take_ownership;
+mod vacant;
+
+pub use async_rent_to_own::AsyncRentToOwn;
+pub use boxed::BoxedRentToOwn;
+pub use cell::RentToOwnCell;
+pub use clone_on_take::{CloneOnTake, CloneTrackingRentToOwn};
+pub use hot_swap::{HotSwap, Retired};
+pub use lease::Lease;
+pub use outcome::Outcome;
+pub use pinned::PinnedRentToOwn;
+pub use rent_to_own_slice::RentToOwnSlice;
+pub use renter::{Offer, Renter};
+pub use shared::{SharedHandle, SharedRentToOwn};
+pub use split::Split;
+pub use take_ownership::TakeOwnership;
+pub use vacant::Vacant;
+
+#[cfg(feature = "derive")]
+pub use rent_to_own_derive::Rentable;
+
+use slot::Slot;
 
 /// A wrapper around a `T` that allows users to conditionally take ownership of
 /// the inner `T` value, or simply use it like a `&mut T` reference.
 ///
 /// See the module documentation for details and examples.
-#[derive(Debug, Hash)]
 pub struct RentToOwn<'a, T: 'a> {
-    inner: &'a mut Option<T>,
+    // Invariant: whenever a `RentToOwn` is accessible, its slot is full. The
+    // lifetime on `take`'s `self` reference upholds this.
+    inner: &'a mut Slot<T>,
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for RentToOwn<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RentToOwn").field("inner", &**self).finish()
+    }
+}
+
+impl<'a, T: Hash> Hash for RentToOwn<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
 }
 
 impl<'a, T> Deref for RentToOwn<'a, T> {
     type Target = T;
+    #[inline]
     fn deref(&self) -> &T {
-        self.inner.as_ref().unwrap()
+        unsafe { self.inner.get() }
     }
 }
 
 impl<'a, T> DerefMut for RentToOwn<'a, T> {
+    #[inline]
     fn deref_mut(&mut self) -> &mut T {
-        self.inner.as_mut().unwrap()
+        unsafe { self.inner.get_mut() }
     }
 }
 
@@ -164,7 +228,7 @@ impl<'a, T: 'a> RentToOwn<'a, T> {
     /// The return value is a pair of:
     ///
     /// 1. If the closure took ownership of the inner value, `None`, otherwise
-    /// `Some(inner)`.
+    ///    `Some(inner)`.
     ///
     /// 2. The value returned by the closure.
     ///
@@ -173,12 +237,53 @@ impl<'a, T: 'a> RentToOwn<'a, T> {
     where
         F: for<'b> FnOnce(&'b mut RentToOwn<'b, T>) -> U,
     {
-        let mut inner = Some(inner);
+        let mut inner = Slot::new(inner);
         let u = {
             let mut me = RentToOwn { inner: &mut inner };
             f(&mut me)
         };
-        (inner, u)
+        (inner.into_inner(), u)
+    }
+
+    /// Like `with`, but if `f` panics, catch the panic and still give back the
+    /// inner value if `f` hadn't taken ownership of it yet.
+    ///
+    /// The return value is a pair of:
+    ///
+    /// 1. If the closure took ownership of the inner value, `None`, otherwise
+    ///    `Some(inner)`. This is the case whether or not the closure panicked.
+    ///
+    /// 2. `Ok` of the value returned by the closure, or `Err` of the panic's
+    ///    payload if the closure panicked.
+    ///
+    /// Note that if `f` panics in the middle of mutating the inner value, then
+    /// the value that is given back may be in whatever partially-updated state
+    /// `f` left it in. It is up to the caller to decide whether that is
+    /// acceptable for their `T` type.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    ///
+    /// let (orig, result) = RentToOwn::with_catch_unwind(5, |x| {
+    ///     if **x == 5 {
+    ///         panic!("oh no!");
+    ///     }
+    ///     x.take()
+    /// });
+    ///
+    /// assert_eq!(orig, Some(5));
+    /// assert!(result.is_err());
+    /// ```
+    pub fn with_catch_unwind<F, U>(inner: T, f: F) -> (Option<T>, Result<U, Box<dyn Any + Send>>)
+    where
+        F: for<'b> FnOnce(&'b mut RentToOwn<'b, T>) -> U,
+    {
+        let mut inner = Slot::new(inner);
+        let u = {
+            let mut me = RentToOwn { inner: &mut inner };
+            panic::catch_unwind(AssertUnwindSafe(|| f(&mut me)))
+        };
+        (inner.into_inner(), u)
     }
 }
 
@@ -214,7 +319,7 @@ impl<'a, T> RentToOwn<'a, T> {
     /// Attempting to compile that example results in a compilation error:
     ///
     /// ```text
-    ///	error[E0502]: cannot borrow `*outer` as immutable because it is also borrowed as mutable
+    /// error[E0502]: cannot borrow `*outer` as immutable because it is also borrowed as mutable
     ///    --> src/lib.rs:18:21
     ///    |
     /// 11 |     let inner = outer.take();
@@ -232,13 +337,86 @@ impl<'a, T> RentToOwn<'a, T> {
     /// becuase [`&'a mut T` is invariant in `T`](https://doc.rust-lang.org/nomicon/subtyping.html).
     ///
     pub fn take(&'a mut self) -> T {
-        self.inner.take().unwrap()
+        unsafe { self.inner.take() }
+    }
+
+    /// Conditionally take ownership of the inner `T` value, depending on
+    /// whether `f` succeeds or not.
+    ///
+    /// The inner value is moved into `f`. If `f` returns `Ok`, then ownership
+    /// has been taken, just like with `take`. If `f` returns `Err((inner,
+    /// error))`, then `inner` is put back into the `RentToOwn`, and whoever
+    /// called `RentToOwn::with` gets it back.
+    ///
+    /// Like `take`, this forces the mutable borrow to last for the rest of the
+    /// `RentToOwn`'s existence, regardless of whether `f` succeeded or not.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    ///
+    /// let (orig, result) = RentToOwn::with(String::from("not a number"), |s| {
+    ///     s.try_take_with(|s| match s.parse::<u32>() {
+    ///         Ok(n) => Ok(n),
+    ///         Err(e) => Err((s, e)),
+    ///     })
+    /// });
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(orig, Some(String::from("not a number")));
+    /// ```
+    pub fn try_take_with<F, U, E>(&'a mut self, f: F) -> Result<U, E>
+    where
+        F: FnOnce(T) -> Result<U, (T, E)>,
+    {
+        match f(unsafe { self.inner.take() }) {
+            Ok(u) => Ok(u),
+            Err((inner, e)) => {
+                self.inner.put(inner);
+                Err(e)
+            }
+        }
+    }
+
+    /// Replace the inner `T` value with `value`, and return the old inner
+    /// value.
+    ///
+    /// Unlike `take`, this leaves the `RentToOwn` populated, so it can keep
+    /// being used afterwards. See also `Option::replace`.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    ///
+    /// let (orig, old) = RentToOwn::with(5, |x| {
+    ///     let old = x.replace(6);
+    ///     assert_eq!(**x, 6);
+    ///     old
+    /// });
+    ///
+    /// assert_eq!(orig, Some(6));
+    /// assert_eq!(old, 5);
+    /// ```
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(&mut **self, value)
+    }
+
+    /// Swap the inner `T` value with `other`.
+    ///
+    /// Like `replace`, this leaves the `RentToOwn` populated, so it can keep
+    /// being used afterwards. See also `std::mem::swap`.
+    pub fn swap(&mut self, other: &mut T) {
+        mem::swap(&mut **self, other)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::RentToOwn;
+    use std::mem;
+
+    #[test]
+    fn is_pointer_sized() {
+        assert_eq!(mem::size_of::<RentToOwn<u64>>(), mem::size_of::<usize>());
+    }
 
     #[test]
     fn it_derefs() {
@@ -279,4 +457,67 @@ mod tests {
         let (orig, _) = RentToOwn::with(5, |x| x.take());
         assert!(orig.is_none());
     }
+
+    #[test]
+    fn try_take_with_takes_on_success() {
+        let (orig, result) = RentToOwn::with(5, |x| x.try_take_with(|x| Ok::<_, (_, ())>(x + 1)));
+        assert!(orig.is_none());
+        assert_eq!(result, Ok(6));
+    }
+
+    #[test]
+    fn try_take_with_gives_back_on_failure() {
+        let (orig, result) = RentToOwn::with(5, |x| x.try_take_with(|x| Err::<(), _>((x, "nope"))));
+        assert_eq!(orig, Some(5));
+        assert_eq!(result, Err("nope"));
+    }
+
+    #[test]
+    fn replace_leaves_new_value() {
+        let (orig, old) = RentToOwn::with(5, |x| {
+            let old = x.replace(6);
+            assert_eq!(**x, 6);
+            old
+        });
+        assert_eq!(orig, Some(6));
+        assert_eq!(old, 5);
+    }
+
+    #[test]
+    fn swap_leaves_other_value() {
+        let mut other = 6;
+        let (orig, _) = RentToOwn::with(5, |x| {
+            x.swap(&mut other);
+            assert_eq!(**x, 6);
+        });
+        assert_eq!(orig, Some(6));
+        assert_eq!(other, 5);
+    }
+
+    #[test]
+    fn with_catch_unwind_gives_back_untaken_ownership_on_panic() {
+        let (orig, result) = RentToOwn::with_catch_unwind(5, |x| {
+            **x = 6;
+            panic!("oh no!")
+        });
+        assert_eq!(orig, Some(6));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn with_catch_unwind_does_not_give_back_taken_ownership_on_panic() {
+        let (orig, result) = RentToOwn::with_catch_unwind(5, |x| {
+            let _x = x.take();
+            panic!("oh no!")
+        });
+        assert!(orig.is_none());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn with_catch_unwind_returns_closures_result() {
+        let (orig, result) = RentToOwn::with_catch_unwind(5, |x| **x + 1);
+        assert_eq!(orig, Some(5));
+        assert_eq!(result.unwrap(), 6);
+    }
 }
@@ -0,0 +1,159 @@
+//! Renting out several values at once.
+
+use super::{RentToOwn, Slot};
+
+impl<'a, A: 'a> RentToOwn<'a, A> {
+    /// Give the function `f` the option to take ownership of either, both, or
+    /// neither of `a` and `b`.
+    ///
+    /// This is like `RentToOwn::with`, but for two values that can be taken
+    /// independently of each other. See the `with_n!` macro for renting out
+    /// any number of values.
+    ///
+    /// The return value is a pair of:
+    ///
+    /// 1. A pair of, for each of `a` and `b`, `None` if the closure took
+    ///    ownership of it, otherwise `Some` of it.
+    ///
+    /// 2. The value returned by the closure.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    ///
+    /// let ((producer, consumer), _) = RentToOwn::with2(vec![1, 2, 3], vec![], |p, c| {
+    ///     c.extend(p.take());
+    /// });
+    ///
+    /// assert_eq!(producer, None);
+    /// assert_eq!(consumer, Some(vec![1, 2, 3]));
+    /// ```
+    pub fn with2<B, F, U>(a: A, b: B, f: F) -> ((Option<A>, Option<B>), U)
+    where
+        F: for<'b, 'c> FnOnce(&'b mut RentToOwn<'b, A>, &'c mut RentToOwn<'c, B>) -> U,
+    {
+        let mut a = Slot::new(a);
+        let mut b = Slot::new(b);
+        let u = {
+            let mut a = RentToOwn { inner: &mut a };
+            let mut b = RentToOwn { inner: &mut b };
+            f(&mut a, &mut b)
+        };
+        ((a.into_inner(), b.into_inner()), u)
+    }
+}
+
+/// Give a closure the option to take ownership of each of any number of
+/// values, independently.
+///
+/// `with_n!((a, b, ...), |ra, rb, ...| body)` is like `RentToOwn::with`, but
+/// for many values: each value is wrapped in its own `RentToOwn` and bound to
+/// the corresponding closure parameter. It evaluates to a pair of:
+///
+/// 1. A tuple of, for each value, `None` if the closure took ownership of it,
+///    otherwise `Some` of it.
+///
+/// 2. The value of the closure's body.
+///
+/// ```
+/// use rent_to_own::with_n;
+///
+/// let ((a, b, c), sum) = with_n!((1, 2, 3), |a, b, c| {
+///     let sum = **a + **b + **c;
+///     b.take();
+///     sum
+/// });
+///
+/// assert_eq!((a, b, c), (Some(1), None, Some(3)));
+/// assert_eq!(sum, 6);
+/// ```
+#[macro_export]
+macro_rules! with_n {
+    // Internal rules, which wrap the values in nested `RentToOwn::with` calls.
+    // Each closure moves the outer closures' `RentToOwn`s into itself, since
+    // it can't take them by reference and still take ownership of their
+    // values, but it captures everything else in the body as usual.
+    (@nest [$($bound:ident)*] $arg:ident $($rest:ident)* ; $body:expr) => {
+        $crate::RentToOwn::with($arg, |$arg| {
+            $( let $bound = $bound; )*
+            $crate::with_n!(@nest [$($bound)* $arg] $($rest)* ; $body)
+        })
+    };
+    (@nest [$($bound:ident)*] ; $body:expr) => {
+        $body
+    };
+
+    // Internal rules, which flatten the nested results into a tuple.
+    (@flatten $nested:expr ; $($done:ident)* ; $first:ident $($rest:ident)*) => {{
+        let ($first, rest) = $nested;
+        $crate::with_n!(@flatten rest ; $($done)* $first ; $($rest)*)
+    }};
+    (@flatten $nested:expr ; $($done:ident)* ; ) => {
+        (($($done,)*), $nested)
+    };
+
+    (($($value:expr),+ $(,)?), |$($arg:ident),+ $(,)?| $body:expr) => {{
+        // Evaluate every value before binding any of the closure's parameter
+        // names, since a value expression may mention one of those names.
+        let ($($arg,)+) = ($($value,)+);
+        let nested = $crate::with_n!(@nest [] $($arg)+ ; $body);
+        $crate::with_n!(@flatten nested ; ; $($arg)+)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RentToOwn;
+
+    #[test]
+    fn with2_takes_independently() {
+        let ((a, b), _) = RentToOwn::with2(1, "b", |a, _| a.take());
+        assert_eq!((a, b), (None, Some("b")));
+
+        let ((a, b), _) = RentToOwn::with2(1, "b", |_, b| b.take());
+        assert_eq!((a, b), (Some(1), None));
+
+        let ((a, b), _) = RentToOwn::with2(1, "b", |a, b| (a.take(), b.take()));
+        assert_eq!((a, b), (None, None));
+    }
+
+    #[test]
+    fn with_n_single_value() {
+        let ((a,), x) = with_n!((5,), |a| **a + 1);
+        assert_eq!(a, Some(5));
+        assert_eq!(x, 6);
+    }
+
+    #[test]
+    fn with_n_borrows_captures() {
+        let mut log = vec![];
+        let ((a, b), _) = with_n!((1, 2), |a, b| {
+            log.push(**a);
+            log.push(b.take());
+        });
+        assert_eq!((a, b), (Some(1), None));
+        assert_eq!(log, [1, 2]);
+    }
+
+    #[test]
+    fn with_n_values_named_like_other_params() {
+        let a = 1;
+        let b = 2;
+        let ((x, y), seen) = with_n!((b, a), |a, b| (**a, **b));
+        assert_eq!(seen, (2, 1));
+        assert_eq!((x, y), (Some(2), Some(1)));
+    }
+
+    #[test]
+    fn with_n_many_values() {
+        let ((a, b, c, d), _) = with_n!((1, String::from("b"), 3.0, vec![4]), |a, b, c, d| {
+            **a += 1;
+            b.take();
+            **c *= 2.0;
+            d.take();
+        });
+        assert_eq!(a, Some(2));
+        assert_eq!(b, None);
+        assert_eq!(c, Some(6.0));
+        assert_eq!(d, None);
+    }
+}
@@ -0,0 +1,103 @@
+//! `Outcome<T, U>`: whether `RentToOwn::run`'s closure took ownership.
+
+use super::{RentToOwn, Slot};
+
+/// The outcome of `RentToOwn::run`: either the closure kept the value, in which
+/// case it is given back, or the closure took it.
+///
+/// Unlike the `(Option<T>, U)` pair returned by `RentToOwn::with`, the
+/// closure's result and what happened to the value can't be matched on
+/// separately and get out of sync with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome<T, U> {
+    /// The closure did not take ownership of the value. Here is the value, and
+    /// the closure's result.
+    Kept(T, U),
+    /// The closure took ownership of the value. Here is the closure's result.
+    Taken(U),
+}
+
+impl<T, U> Outcome<T, U> {
+    /// Returns `true` if the closure took ownership of the value.
+    pub fn is_taken(&self) -> bool {
+        match *self {
+            Outcome::Kept(..) => false,
+            Outcome::Taken(_) => true,
+        }
+    }
+
+    /// Get a reference to the closure's result.
+    pub fn result(&self) -> &U {
+        match *self {
+            Outcome::Kept(_, ref u) | Outcome::Taken(ref u) => u,
+        }
+    }
+
+    /// Convert this outcome into the same pair that `RentToOwn::with` returns.
+    pub fn into_parts(self) -> (Option<T>, U) {
+        match self {
+            Outcome::Kept(t, u) => (Some(t), u),
+            Outcome::Taken(u) => (None, u),
+        }
+    }
+}
+
+impl<'a, T: 'a> RentToOwn<'a, T> {
+    /// Give the function `f` the option to take ownership of `inner`, and
+    /// report the result as an `Outcome`.
+    ///
+    /// This is exactly like `RentToOwn::with`, except for the return type.
+    ///
+    /// ```
+    /// use rent_to_own::{Outcome, RentToOwn};
+    ///
+    /// let outcome = RentToOwn::run(String::from("hello"), |s| {
+    ///     if s.len() > 3 {
+    ///         Some(s.take())
+    ///     } else {
+    ///         None
+    ///     }
+    /// });
+    ///
+    /// match outcome {
+    ///     Outcome::Kept(s, _) => println!("still have {}", s),
+    ///     Outcome::Taken(s) => println!("gave away {:?}", s),
+    /// }
+    /// ```
+    pub fn run<F, U>(inner: T, f: F) -> Outcome<T, U>
+    where
+        F: for<'b> FnOnce(&'b mut RentToOwn<'b, T>) -> U,
+    {
+        let mut inner = Slot::new(inner);
+        let u = {
+            let mut me = RentToOwn { inner: &mut inner };
+            f(&mut me)
+        };
+        match inner.into_inner() {
+            Some(t) => Outcome::Kept(t, u),
+            None => Outcome::Taken(u),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Outcome;
+    use crate::RentToOwn;
+
+    #[test]
+    fn run_kept() {
+        let outcome = RentToOwn::run(5, |x| **x + 1);
+        assert_eq!(outcome, Outcome::Kept(5, 6));
+        assert!(!outcome.is_taken());
+        assert_eq!(*outcome.result(), 6);
+    }
+
+    #[test]
+    fn run_taken() {
+        let outcome = RentToOwn::run(5, |x| x.take() + 1);
+        assert_eq!(outcome, Outcome::Taken(6));
+        assert!(outcome.is_taken());
+        assert_eq!(outcome.into_parts(), (None, 6));
+    }
+}
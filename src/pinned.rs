@@ -0,0 +1,160 @@
+//! `PinnedRentToOwn<T>`: conditionally taking ownership of pinned values.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+
+use super::{RentToOwn, Slot};
+
+/// A wrapper around a pinned `T` that allows users to conditionally take
+/// ownership of the inner `T` value, or simply use it like a `Pin<&mut T>`
+/// reference.
+///
+/// A `RentToOwn<T>` dereferences mutably to its inner `T`, which would let the
+/// value be moved out from under a pin. `PinnedRentToOwn<T>` keeps the inner
+/// value pinned in a `Pin<Box<T>>` instead: it only gives out `Pin<&mut T>`
+/// references, and ownership of the `Pin<Box<T>>` itself can be taken, unless
+/// `T: Unpin`, in which case it behaves just like `RentToOwn<T>`.
+///
+/// Construct one with `RentToOwn::with_pinned`.
+pub struct PinnedRentToOwn<'a, T: 'a> {
+    // Invariant: whenever a `PinnedRentToOwn` is accessible, its slot is full.
+    // The lifetime on `take`'s and `take_pinned`'s `self` reference upholds
+    // this.
+    inner: &'a mut Slot<Pin<Box<T>>>,
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for PinnedRentToOwn<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PinnedRentToOwn")
+            .field("inner", &**self)
+            .finish()
+    }
+}
+
+impl<'a, T> Deref for PinnedRentToOwn<'a, T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { self.inner.get() }
+    }
+}
+
+impl<'a, T: Unpin> DerefMut for PinnedRentToOwn<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.inner.get_mut() }
+    }
+}
+
+impl<'a, T> PinnedRentToOwn<'a, T> {
+    /// Get a pinned mutable reference to the inner `T` value.
+    #[inline]
+    pub fn as_pin_mut(&mut self) -> Pin<&mut T> {
+        unsafe { self.inner.get_mut() }.as_mut()
+    }
+
+    /// Take ownership of the inner value, still pinned in its box.
+    ///
+    /// Like `RentToOwn::take`, the lifetime on the `self` reference statically
+    /// disallows use-after-take.
+    pub fn take_pinned(&'a mut self) -> Pin<Box<T>> {
+        unsafe { self.inner.take() }
+    }
+}
+
+impl<'a, T: Unpin> PinnedRentToOwn<'a, T> {
+    /// Take ownership of the inner `T` value.
+    ///
+    /// This is only possible when `T: Unpin`, since otherwise the value must
+    /// not be moved out of its pinned box. Use `take_pinned` for other `T`s.
+    pub fn take(&'a mut self) -> T {
+        *Pin::into_inner(self.take_pinned())
+    }
+}
+
+impl<'a, T: 'a> RentToOwn<'a, T> {
+    /// Give the function `f` the option to take ownership of the pinned
+    /// `inner` value.
+    ///
+    /// This is the pinned version of `RentToOwn::with`: it creates a
+    /// `PinnedRentToOwn` from the given `inner` value and then invokes the
+    /// function `f` with it.
+    ///
+    /// The return value is a pair of:
+    ///
+    /// 1. If the closure took ownership of the inner value, `None`, otherwise
+    ///    `Some(inner)`, still pinned.
+    ///
+    /// 2. The value returned by the closure.
+    ///
+    /// ```
+    /// use rent_to_own::{PinnedRentToOwn, RentToOwn};
+    /// use std::future::Future;
+    /// use std::pin::Pin;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// // Poll the future once, and only take ownership of it if it completed.
+    /// fn poll_once<'a, F: Future>(
+    ///     fut: &'a mut PinnedRentToOwn<'a, F>,
+    /// ) -> Option<(F::Output, Pin<Box<F>>)> {
+    ///     let mut cx = Context::from_waker(Waker::noop());
+    ///     match fut.as_pin_mut().poll(&mut cx) {
+    ///         Poll::Ready(x) => Some((x, fut.take_pinned())),
+    ///         Poll::Pending => None,
+    ///     }
+    /// }
+    ///
+    /// let (orig, result) = RentToOwn::with_pinned(Box::pin(async { 5 }), poll_once);
+    /// assert!(orig.is_none());
+    /// assert_eq!(result.unwrap().0, 5);
+    /// ```
+    pub fn with_pinned<F, U>(inner: Pin<Box<T>>, f: F) -> (Option<Pin<Box<T>>>, U)
+    where
+        F: for<'b> FnOnce(&'b mut PinnedRentToOwn<'b, T>) -> U,
+    {
+        let mut inner = Slot::new(inner);
+        let u = {
+            let mut me = PinnedRentToOwn { inner: &mut inner };
+            f(&mut me)
+        };
+        (inner.into_inner(), u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RentToOwn;
+    use std::marker::PhantomPinned;
+
+    struct NotUnpin(u32, PhantomPinned);
+
+    #[test]
+    fn with_pinned_gives_back_untaken_ownership() {
+        let (orig, x) = RentToOwn::with_pinned(Box::pin(NotUnpin(5, PhantomPinned)), |x| {
+            // Safety: we don't move anything out of the pinned value.
+            unsafe { x.as_pin_mut().get_unchecked_mut().0 += 1 };
+            x.0
+        });
+        assert_eq!(orig.unwrap().0, 6);
+        assert_eq!(x, 6);
+    }
+
+    #[test]
+    fn with_pinned_does_not_give_back_taken_ownership() {
+        let (orig, x) =
+            RentToOwn::with_pinned(Box::pin(NotUnpin(5, PhantomPinned)), |x| x.take_pinned());
+        assert!(orig.is_none());
+        assert_eq!(x.0, 5);
+    }
+
+    #[test]
+    fn unpin_values_deref_mut_and_take() {
+        let (orig, x) = RentToOwn::with_pinned(Box::pin(5), |x| {
+            **x += 1;
+            x.take()
+        });
+        assert!(orig.is_none());
+        assert_eq!(x, 6);
+    }
+}
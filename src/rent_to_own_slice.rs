@@ -0,0 +1,204 @@
+//! `RentToOwnSlice<T>`: conditionally taking ownership of individual elements
+//! of a collection.
+
+use std::ops::{Index, IndexMut};
+
+use super::RentToOwn;
+
+/// A wrapper around a sequence of `T` values that allows users to
+/// conditionally take ownership of each element independently, or simply use
+/// them like a `&mut [T]` slice.
+///
+/// Because each element may or may not have been taken, the lifetime trickery
+/// used by `RentToOwn<T>` can't statically prevent use-after-take
+/// here. Instead, `get`, `get_mut`, and `take` return `None` for elements that
+/// have already been taken, and indexing panics.
+///
+/// Construct one with `RentToOwn::with_slice`.
+#[derive(Debug, Hash)]
+pub struct RentToOwnSlice<'a, T: 'a> {
+    inner: &'a mut [Option<T>],
+}
+
+impl<'a, T> RentToOwnSlice<'a, T> {
+    /// Get the number of elements, including the ones that have already been
+    /// taken.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if there are no elements, taken or otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns `true` if the element at `index` has been taken, or if `index`
+    /// is out of bounds.
+    ///
+    /// That is, `is_taken(index)` is `true` exactly when `get(index)` is
+    /// `None`.
+    pub fn is_taken(&self, index: usize) -> bool {
+        self.inner.get(index).is_none_or(Option::is_none)
+    }
+
+    /// Get a reference to the element at `index`, or `None` if it is out of
+    /// bounds or has been taken.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.inner.get(index).and_then(Option::as_ref)
+    }
+
+    /// Get a mutable reference to the element at `index`, or `None` if it is
+    /// out of bounds or has been taken.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.inner.get_mut(index).and_then(Option::as_mut)
+    }
+
+    /// Take ownership of the element at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds or the element has already
+    /// been taken.
+    pub fn take(&mut self, index: usize) -> Option<T> {
+        self.inner.get_mut(index).and_then(Option::take)
+    }
+
+    /// Take ownership of every remaining element for which `predicate`
+    /// returns `true`, in order.
+    pub fn take_where<P>(&mut self, mut predicate: P) -> Vec<T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.inner
+            .iter_mut()
+            .filter(|elem| elem.as_ref().is_some_and(&mut predicate))
+            .filter_map(Option::take)
+            .collect()
+    }
+
+    /// Iterate over the elements that have not been taken, along with their
+    /// indices.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.inner
+            .iter()
+            .enumerate()
+            .filter_map(|(i, elem)| elem.as_ref().map(|elem| (i, elem)))
+    }
+
+    /// Mutably iterate over the elements that have not been taken, along with
+    /// their indices.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.inner
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, elem)| elem.as_mut().map(|elem| (i, elem)))
+    }
+}
+
+impl<'a, T> Index<usize> for RentToOwnSlice<'a, T> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        self.get(index)
+            .expect("index out of bounds or element already taken")
+    }
+}
+
+impl<'a, T> IndexMut<usize> for RentToOwnSlice<'a, T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index)
+            .expect("index out of bounds or element already taken")
+    }
+}
+
+impl<'a, T: 'a> RentToOwn<'a, T> {
+    /// Give the function `f` the option to take ownership of any of the
+    /// elements of `inner`.
+    ///
+    /// That is, create a `RentToOwnSlice` from the given `inner` elements and
+    /// then invoke the function `f` with it.
+    ///
+    /// The return value is a pair of:
+    ///
+    /// 1. The elements that the closure did not take ownership of, in their
+    ///    original order.
+    ///
+    /// 2. The value returned by the closure.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    ///
+    /// let (kept, taken) = RentToOwn::with_slice(vec![1, 2, 3, 4, 5], |xs| {
+    ///     xs.take_where(|x| x % 2 == 0)
+    /// });
+    ///
+    /// assert_eq!(kept, [1, 3, 5]);
+    /// assert_eq!(taken, [2, 4]);
+    /// ```
+    pub fn with_slice<F, U>(inner: Vec<T>, f: F) -> (Vec<T>, U)
+    where
+        F: FnOnce(&mut RentToOwnSlice<T>) -> U,
+    {
+        let mut inner: Vec<_> = inner.into_iter().map(Some).collect();
+        let u = {
+            let mut me = RentToOwnSlice { inner: &mut inner };
+            f(&mut me)
+        };
+        (inner.into_iter().flatten().collect(), u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RentToOwn;
+
+    #[test]
+    fn with_slice_gives_back_untaken_elements() {
+        let (kept, taken) = RentToOwn::with_slice(vec![1, 2, 3], |xs| xs.take(1));
+        assert_eq!(kept, [1, 3]);
+        assert_eq!(taken, Some(2));
+    }
+
+    #[test]
+    fn take_twice_returns_none() {
+        RentToOwn::with_slice(vec![1, 2, 3], |xs| {
+            assert_eq!(xs.take(0), Some(1));
+            assert!(xs.is_taken(0));
+            assert_eq!(xs.take(0), None);
+            assert_eq!(xs.get(0), None);
+            assert_eq!(xs.take(3), None);
+        });
+    }
+
+    #[test]
+    fn is_taken_out_of_bounds() {
+        RentToOwn::with_slice(vec![1], |xs| {
+            assert!(!xs.is_taken(0));
+            assert!(xs.is_taken(1));
+        });
+    }
+
+    #[test]
+    fn it_indexes_mut() {
+        let (kept, _) = RentToOwn::with_slice(vec![1, 2, 3], |xs| {
+            xs[2] = 6;
+            assert_eq!(xs[2], 6);
+        });
+        assert_eq!(kept, [1, 2, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn indexing_taken_element_panics() {
+        RentToOwn::with_slice(vec![1, 2, 3], |xs| {
+            xs.take(1);
+            xs[1]
+        });
+    }
+
+    #[test]
+    fn iter_skips_taken_elements() {
+        RentToOwn::with_slice(vec![1, 2, 3], |xs| {
+            xs.take(1);
+            assert_eq!(xs.len(), 3);
+            assert_eq!(xs.iter().collect::<Vec<_>>(), [(0, &1), (2, &3)]);
+        });
+    }
+}
@@ -0,0 +1,173 @@
+//! `Renter<T>`: offering ownership of a value to many closures in turn.
+
+use std::fmt;
+
+use super::{RentToOwn, Slot};
+
+/// The result of offering ownership of a `Renter`'s value to a closure with
+/// `Renter::offer`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Offer<U> {
+    /// The closure didn't take ownership of the value. Here is the closure's
+    /// result.
+    Kept(U),
+    /// The closure took ownership of the value. Here is the closure's result.
+    Taken(U),
+    /// The value had already been taken, so the closure was not called.
+    Unavailable,
+}
+
+impl<U> Offer<U> {
+    /// Returns `true` if this offer's closure took ownership of the value.
+    pub fn is_taken(&self) -> bool {
+        match *self {
+            Offer::Taken(_) => true,
+            Offer::Kept(_) | Offer::Unavailable => false,
+        }
+    }
+
+    /// Get the closure's result, or `None` if the closure was not called.
+    pub fn into_result(self) -> Option<U> {
+        match self {
+            Offer::Kept(u) | Offer::Taken(u) => Some(u),
+            Offer::Unavailable => None,
+        }
+    }
+}
+
+/// A value whose ownership can be offered to a sequence of closures, until one
+/// of them takes it.
+///
+/// This is a reusable version of `RentToOwn::with`: each call to `offer` gives
+/// a closure a `RentToOwn` wrapping the value, just like `with` does, and
+/// reports whether the closure took ownership of it.
+///
+/// ```
+/// use rent_to_own::{Offer, Renter};
+///
+/// let mut renter = Renter::new(String::from("event"));
+///
+/// // Each handler may either inspect the value, or claim it.
+/// for wanted in 3..7 {
+///     let offer = renter.offer(|s| if s.len() == wanted { Some(s.take()) } else { None });
+///     match offer {
+///         Offer::Kept(None) => println!("handler {} passed", wanted),
+///         Offer::Taken(Some(s)) => println!("handler {} claimed {}", wanted, s),
+///         Offer::Unavailable => println!("handler {} was too late", wanted),
+///         _ => unreachable!(),
+///     }
+/// }
+///
+/// assert!(renter.is_taken());
+/// assert_eq!(renter.into_remaining(), None);
+/// ```
+pub struct Renter<T> {
+    inner: Slot<T>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Renter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Renter")
+            .field("inner", &self.get())
+            .finish()
+    }
+}
+
+impl<T> Renter<T> {
+    /// Construct a new `Renter` holding the given value.
+    pub fn new(value: T) -> Renter<T> {
+        Renter {
+            inner: Slot::new(value),
+        }
+    }
+
+    /// Give the function `f` the option to take ownership of the value, if it
+    /// hasn't been taken yet.
+    pub fn offer<F, U>(&mut self, f: F) -> Offer<U>
+    where
+        F: for<'b> FnOnce(&'b mut RentToOwn<'b, T>) -> U,
+    {
+        if !self.inner.is_full() {
+            return Offer::Unavailable;
+        }
+        let u = {
+            let mut me = RentToOwn {
+                inner: &mut self.inner,
+            };
+            f(&mut me)
+        };
+        if self.inner.is_full() {
+            Offer::Kept(u)
+        } else {
+            Offer::Taken(u)
+        }
+    }
+
+    /// Returns `true` if the value has been taken.
+    pub fn is_taken(&self) -> bool {
+        !self.inner.is_full()
+    }
+
+    /// Get a reference to the value, or `None` if it has been taken.
+    pub fn get(&self) -> Option<&T> {
+        if self.inner.is_full() {
+            Some(unsafe { self.inner.get() })
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the value, or `None` if it has been taken.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if self.inner.is_full() {
+            Some(unsafe { self.inner.get_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Consume the `Renter`, and get the value back, or `None` if it was
+    /// taken.
+    pub fn into_remaining(self) -> Option<T> {
+        self.inner.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Offer, Renter};
+
+    #[test]
+    fn offers_until_taken() {
+        let mut renter = Renter::new(5);
+        assert_eq!(renter.offer(|x| **x += 1), Offer::Kept(()));
+        assert_eq!(renter.get(), Some(&6));
+        assert_eq!(renter.offer(|x| x.take()), Offer::Taken(6));
+        assert!(renter.is_taken());
+        assert_eq!(renter.offer(|x| x.take()), Offer::Unavailable);
+        assert_eq!(renter.into_remaining(), None);
+    }
+
+    #[test]
+    fn into_remaining_gives_back_untaken() {
+        let mut renter = Renter::new(5);
+        for _ in 0..3 {
+            assert!(!renter.offer(|x| **x += 1).is_taken());
+        }
+        *renter.get_mut().unwrap() += 1;
+        assert_eq!(renter.into_remaining(), Some(9));
+    }
+
+    #[test]
+    fn handlers_in_a_loop() {
+        let mut renter = Renter::new(String::from("claim me"));
+        let mut claimed_by = None;
+        for i in 0..5 {
+            let offer = renter.offer(|s| if i == 3 { Some(s.take()) } else { None });
+            if offer.is_taken() {
+                claimed_by = Some((i, offer.into_result().unwrap().unwrap()));
+            }
+        }
+        assert_eq!(claimed_by, Some((3, String::from("claim me"))));
+    }
+}
@@ -0,0 +1,86 @@
+//! Running a `RentToOwn` closure on another thread.
+
+use std::panic;
+use std::thread;
+
+use super::{RentToOwn, Slot};
+
+impl<'a, T: 'a + Send> RentToOwn<'a, T> {
+    /// Give the function `f` the option to take ownership of `inner`, and run
+    /// `f` on a new scoped thread.
+    ///
+    /// This is exactly like `RentToOwn::with`, except that `f` runs on another
+    /// thread, and the current thread blocks until it is done. Because the
+    /// thread is scoped, neither `inner` nor `f` need to be `'static`. If `f`
+    /// panics, the panic is propagated to the current thread.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    /// use std::thread;
+    ///
+    /// let caller = thread::current().id();
+    ///
+    /// let (orig, ran_elsewhere) = RentToOwn::with_scoped(vec![1, 2, 3], |v| {
+    ///     v.push(4);
+    ///     thread::current().id() != caller
+    /// });
+    ///
+    /// assert_eq!(orig, Some(vec![1, 2, 3, 4]));
+    /// assert!(ran_elsewhere);
+    /// ```
+    pub fn with_scoped<F, U>(inner: T, f: F) -> (Option<T>, U)
+    where
+        F: for<'b> FnOnce(&'b mut RentToOwn<'b, T>) -> U + Send,
+        U: Send,
+    {
+        let mut inner = Slot::new(inner);
+        let u = {
+            let inner = &mut inner;
+            thread::scope(|s| {
+                let worker = s.spawn(move || {
+                    let mut me = RentToOwn { inner };
+                    f(&mut me)
+                });
+                worker.join().unwrap_or_else(|e| panic::resume_unwind(e))
+            })
+        };
+        (inner.into_inner(), u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RentToOwn;
+
+    #[test]
+    fn with_scoped_gives_back_untaken_ownership() {
+        let (orig, x) = RentToOwn::with_scoped(5, |x| {
+            **x += 1;
+            **x
+        });
+        assert_eq!(orig, Some(6));
+        assert_eq!(x, 6);
+    }
+
+    #[test]
+    fn with_scoped_does_not_give_back_taken_ownership() {
+        let (orig, x) = RentToOwn::with_scoped(5, |x| x.take());
+        assert!(orig.is_none());
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn with_scoped_borrows_from_caller() {
+        let mut log = vec![];
+        let s = String::from("borrowed");
+        let (orig, _) = RentToOwn::with_scoped(&s[..], |x| log.push(x.len()));
+        assert_eq!(orig, Some("borrowed"));
+        assert_eq!(log, [8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "oh no!")]
+    fn with_scoped_propagates_panics() {
+        RentToOwn::with_scoped(5, |_| panic!("oh no!"));
+    }
+}
@@ -0,0 +1,197 @@
+//! `SharedRentToOwn<T>`: conditionally taking ownership across threads.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// A thread-safe slot holding a `T` value, whose ownership may be claimed by
+/// whichever `SharedHandle<T>` takes it first.
+///
+/// The single-threaded `RentToOwn<T>` statically prevents use-after-take with
+/// lifetimes, but that can't work when the value is offered to another
+/// thread. Instead, `SharedRentToOwn<T>` hands out cloneable, `Send`
+/// `SharedHandle<T>`s that can be given to other threads, and those threads may
+/// or may not take ownership of the value. The original holder can query
+/// whether the value was taken with `was_taken`, or take it back with
+/// `try_reclaim` if it wasn't.
+///
+/// If a closure given to `SharedHandle::with` panics, the value is not dropped
+/// or discarded: later calls to `with`, `try_take` and `try_reclaim` still hand
+/// it out, in whatever partially-updated state the closure left it in. It is up
+/// to the caller to decide whether that is acceptable for their `T` type.
+///
+/// ```
+/// use rent_to_own::SharedRentToOwn;
+/// use std::thread;
+///
+/// let shared = SharedRentToOwn::new(String::from("expensive resource"));
+///
+/// let handle = shared.handle();
+/// let worker = thread::spawn(move || {
+///     // Only claim the resource if it is the one we're looking for.
+///     if handle.with(|r| r.starts_with("cheap")).unwrap_or(false) {
+///         handle.try_take()
+///     } else {
+///         None
+///     }
+/// });
+///
+/// assert!(worker.join().unwrap().is_none());
+/// assert!(!shared.was_taken());
+/// assert_eq!(shared.try_reclaim().unwrap(), "expensive resource");
+/// ```
+#[derive(Debug)]
+pub struct SharedRentToOwn<T> {
+    inner: Arc<Mutex<Option<T>>>,
+}
+
+/// A cloneable handle to a `SharedRentToOwn<T>`'s value, through which the
+/// value may be used or taken.
+///
+/// Construct one with `SharedRentToOwn::handle`.
+#[derive(Debug)]
+pub struct SharedHandle<T> {
+    inner: Arc<Mutex<Option<T>>>,
+}
+
+// Poisoning is ignored: a value that was being mutated by a panicking `with`
+// closure is still handed out afterwards, as documented on `SharedRentToOwn`.
+fn lock<T>(inner: &Mutex<Option<T>>) -> MutexGuard<'_, Option<T>> {
+    inner.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<T> SharedRentToOwn<T> {
+    /// Construct a new `SharedRentToOwn` holding the given value.
+    pub fn new(value: T) -> SharedRentToOwn<T> {
+        SharedRentToOwn {
+            inner: Arc::new(Mutex::new(Some(value))),
+        }
+    }
+
+    /// Get a new handle to the value, which may be sent to another thread.
+    pub fn handle(&self) -> SharedHandle<T> {
+        SharedHandle {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Returns `true` if the value has been taken, either by one of the
+    /// handles or by `try_reclaim`.
+    pub fn was_taken(&self) -> bool {
+        lock(&self.inner).is_none()
+    }
+
+    /// Take back ownership of the value, if it hasn't already been taken.
+    ///
+    /// After reclaiming the value, attempts to take it through any of the
+    /// handles will fail.
+    pub fn try_reclaim(&self) -> Option<T> {
+        lock(&self.inner).take()
+    }
+}
+
+impl<T> Clone for SharedHandle<T> {
+    fn clone(&self) -> SharedHandle<T> {
+        SharedHandle {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> SharedHandle<T> {
+    /// Take ownership of the value, if it hasn't already been taken.
+    ///
+    /// At most one of a `SharedRentToOwn`'s handles will ever succeed in
+    /// taking the value.
+    pub fn try_take(&self) -> Option<T> {
+        lock(&self.inner).take()
+    }
+
+    /// Returns `true` if the value has been taken.
+    pub fn was_taken(&self) -> bool {
+        lock(&self.inner).is_none()
+    }
+
+    /// Call `f` with a mutable reference to the value, if it hasn't been
+    /// taken. The value stays locked for the duration of the call.
+    ///
+    /// Deadlocks if `with`, `try_take` or `was_taken` is called on any handle
+    /// to the same value, or `was_taken` or `try_reclaim` on its
+    /// `SharedRentToOwn`, from within `f`.
+    ///
+    /// If `f` panics, the value is kept in whatever state `f` left it in. See
+    /// the `SharedRentToOwn` documentation.
+    pub fn with<F, U>(&self, f: F) -> Option<U>
+    where
+        F: FnOnce(&mut T) -> U,
+    {
+        lock(&self.inner).as_mut().map(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SharedRentToOwn;
+    use std::thread;
+
+    #[test]
+    fn reclaim_untaken() {
+        let shared = SharedRentToOwn::new(5);
+        let handle = shared.handle();
+        thread::spawn(move || handle.with(|x| *x += 1))
+            .join()
+            .unwrap();
+        assert!(!shared.was_taken());
+        assert_eq!(shared.try_reclaim(), Some(6));
+        assert!(shared.was_taken());
+    }
+
+    #[test]
+    fn cannot_reclaim_taken() {
+        let shared = SharedRentToOwn::new(5);
+        let handle = shared.handle();
+        let taken = thread::spawn(move || handle.try_take()).join().unwrap();
+        assert_eq!(taken, Some(5));
+        assert!(shared.was_taken());
+        assert_eq!(shared.try_reclaim(), None);
+    }
+
+    #[test]
+    fn only_one_handle_takes() {
+        let shared = SharedRentToOwn::new(5);
+        let workers: Vec<_> = (0..8)
+            .map(|_| {
+                let handle = shared.handle();
+                thread::spawn(move || handle.try_take())
+            })
+            .collect();
+        let taken: Vec<_> = workers
+            .into_iter()
+            .filter_map(|w| w.join().unwrap())
+            .collect();
+        assert_eq!(taken, [5]);
+    }
+
+    #[test]
+    fn value_survives_panic_in_with() {
+        let shared = SharedRentToOwn::new(vec![1]);
+        let handle = shared.handle();
+        thread::spawn(move || {
+            handle.with(|v| {
+                v.push(2);
+                panic!("oh no!");
+            })
+        })
+        .join()
+        .unwrap_err();
+        assert_eq!(shared.try_reclaim(), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn handles_see_reclaim() {
+        let shared = SharedRentToOwn::new(5);
+        let handle = shared.handle();
+        assert_eq!(shared.try_reclaim(), Some(5));
+        assert!(handle.was_taken());
+        assert_eq!(handle.with(|x| *x), None);
+        assert_eq!(handle.try_take(), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! `Slot<T>`: the storage behind a `RentToOwn<T>`.
+
+use std::mem::{ManuallyDrop, MaybeUninit};
+
+/// Storage for a value that might be taken, like an `Option<T>`, except that
+/// accessing the value does not check whether it is still there.
+///
+/// `RentToOwn` statically guarantees that it is never used after its inner
+/// value has been taken, so every check on deref would be redundant. Instead,
+/// the `full` flag is only consulted when the slot is finally unwrapped with
+/// `into_inner`, or dropped.
+pub(crate) struct Slot<T> {
+    value: MaybeUninit<T>,
+    full: bool,
+}
+
+impl<T> Slot<T> {
+    /// Construct a new, full slot.
+    pub(crate) fn new(value: T) -> Slot<T> {
+        Slot {
+            value: MaybeUninit::new(value),
+            full: true,
+        }
+    }
+
+    /// Returns `true` if the slot holds a value.
+    #[inline]
+    pub(crate) fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// Get the value out of the slot, or `None` if it has been taken.
+    pub(crate) fn into_inner(self) -> Option<T> {
+        let me = ManuallyDrop::new(self);
+        if me.full {
+            // Safety: the slot is full, and we won't touch it again.
+            Some(unsafe { me.value.assume_init_read() })
+        } else {
+            None
+        }
+    }
+
+    /// Get a reference to the value.
+    ///
+    /// Safety: the slot must be full.
+    #[inline]
+    pub(crate) unsafe fn get(&self) -> &T {
+        debug_assert!(self.full);
+        self.value.assume_init_ref()
+    }
+
+    /// Get a mutable reference to the value.
+    ///
+    /// Safety: the slot must be full.
+    #[inline]
+    pub(crate) unsafe fn get_mut(&mut self) -> &mut T {
+        debug_assert!(self.full);
+        self.value.assume_init_mut()
+    }
+
+    /// Take the value, leaving the slot empty.
+    ///
+    /// Safety: the slot must be full.
+    #[inline]
+    pub(crate) unsafe fn take(&mut self) -> T {
+        debug_assert!(self.full);
+        self.full = false;
+        self.value.assume_init_read()
+    }
+
+    /// Put a value into the slot, making it full again.
+    ///
+    /// The slot should be empty; if it is not, its old value is leaked.
+    #[inline]
+    pub(crate) fn put(&mut self, value: T) {
+        debug_assert!(!self.full);
+        self.value = MaybeUninit::new(value);
+        self.full = true;
+    }
+}
+
+impl<T> Drop for Slot<T> {
+    fn drop(&mut self) {
+        if self.full {
+            // Safety: the slot is full, and it is being dropped.
+            unsafe { self.value.assume_init_drop() }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Slot;
+    use std::mem;
+    use std::rc::Rc;
+
+    #[test]
+    fn is_as_small_as_option() {
+        assert_eq!(mem::size_of::<Slot<u64>>(), mem::size_of::<Option<u64>>());
+    }
+
+    #[test]
+    fn drops_value_when_full() {
+        let rc = Rc::new(());
+        drop(Slot::new(rc.clone()));
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn does_not_drop_taken_value() {
+        let rc = Rc::new(());
+        let mut slot = Slot::new(rc.clone());
+        let taken = unsafe { slot.take() };
+        drop(slot);
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(taken);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn into_inner_reports_taken() {
+        let mut slot = Slot::new(5);
+        assert_eq!(unsafe { slot.take() }, 5);
+        assert_eq!(slot.into_inner(), None);
+
+        let mut slot = Slot::new(5);
+        let x = unsafe { slot.take() };
+        slot.put(x + 1);
+        assert_eq!(slot.into_inner(), Some(6));
+    }
+}
@@ -0,0 +1,152 @@
+//! Splitting a `RentToOwn<T>` into `RentToOwn`s of its parts.
+
+use super::{RentToOwn, Slot};
+
+/// What became of the parts of a `RentToOwn` that was split with
+/// `RentToOwn::split`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Split<A, B> {
+    /// Neither part was taken, so they were joined back together and put back
+    /// into the original `RentToOwn`.
+    Joined,
+    /// Only the first part was taken. Here is the second part, which was not.
+    TookFirst(B),
+    /// Only the second part was taken. Here is the first part, which was not.
+    TookSecond(A),
+    /// Both parts were taken.
+    TookBoth,
+}
+
+impl<'a, T> RentToOwn<'a, T> {
+    /// Split the inner `T` value into two parts, and give the function `f` the
+    /// option to take ownership of each part independently.
+    ///
+    /// The `split` function breaks the inner value into its parts, which are
+    /// then given to `f` as two `RentToOwn`s, with the same static
+    /// use-after-take prevention as any other `RentToOwn`. Afterwards, if `f`
+    /// didn't take either part, the `join` function reassembles them and the
+    /// reassembled value is put back into this `RentToOwn`, so whoever called
+    /// `RentToOwn::with` gets it back. Otherwise, the parts that were not taken
+    /// are returned in the `Split`.
+    ///
+    /// To split a value into more than two parts, make one of the parts a
+    /// tuple and split it again.
+    ///
+    /// Like `take`, this forces the mutable borrow to last for the rest of the
+    /// `RentToOwn`'s existence, since its inner value might not be put back.
+    ///
+    /// ```
+    /// use rent_to_own::{RentToOwn, Split};
+    ///
+    /// struct Socket;
+    /// struct Config;
+    ///
+    /// struct Connection {
+    ///     socket: Socket,
+    ///     config: Config,
+    /// }
+    ///
+    /// let conn = Connection { socket: Socket, config: Config };
+    ///
+    /// let (orig, (split, socket)) = RentToOwn::with(conn, |conn| {
+    ///     conn.split(
+    ///         |c| (c.socket, c.config),
+    ///         |socket, config| Connection { socket, config },
+    ///         |socket, _config| socket.take(),
+    ///     )
+    /// });
+    ///
+    /// // The connection was not put back together, but we still get its
+    /// // config back.
+    /// assert!(orig.is_none());
+    /// assert!(matches!(split, Split::TookFirst(Config)));
+    /// ```
+    pub fn split<A, B, S, J, F, U>(&'a mut self, split: S, join: J, f: F) -> (Split<A, B>, U)
+    where
+        S: FnOnce(T) -> (A, B),
+        J: FnOnce(A, B) -> T,
+        F: for<'b, 'c> FnOnce(&'b mut RentToOwn<'b, A>, &'c mut RentToOwn<'c, B>) -> U,
+    {
+        let (a, b) = split(unsafe { self.inner.take() });
+        let mut a = Slot::new(a);
+        let mut b = Slot::new(b);
+        let u = {
+            let mut a = RentToOwn { inner: &mut a };
+            let mut b = RentToOwn { inner: &mut b };
+            f(&mut a, &mut b)
+        };
+        let split = match (a.into_inner(), b.into_inner()) {
+            (Some(a), Some(b)) => {
+                self.inner.put(join(a, b));
+                Split::Joined
+            }
+            (None, Some(b)) => Split::TookFirst(b),
+            (Some(a), None) => Split::TookSecond(a),
+            (None, None) => Split::TookBoth,
+        };
+        (split, u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Split;
+    use crate::RentToOwn;
+
+    fn split_pair<'a>(
+        pair: &'a mut RentToOwn<'a, (i32, i32)>,
+        take_first: bool,
+        take_second: bool,
+    ) -> Split<i32, i32> {
+        let (split, _) = pair.split(
+            |p| p,
+            |a, b| (a, b),
+            |a, b| {
+                if take_first {
+                    a.take();
+                }
+                if take_second {
+                    b.take();
+                }
+            },
+        );
+        split
+    }
+
+    #[test]
+    fn split_joins_untaken_parts() {
+        let (orig, split) = RentToOwn::with((1, 2), |p| split_pair(p, false, false));
+        assert_eq!(orig, Some((1, 2)));
+        assert_eq!(split, Split::Joined);
+    }
+
+    #[test]
+    fn split_gives_back_untaken_parts() {
+        let (orig, split) = RentToOwn::with((1, 2), |p| split_pair(p, true, false));
+        assert!(orig.is_none());
+        assert_eq!(split, Split::TookFirst(2));
+
+        let (orig, split) = RentToOwn::with((1, 2), |p| split_pair(p, false, true));
+        assert!(orig.is_none());
+        assert_eq!(split, Split::TookSecond(1));
+
+        let (orig, split) = RentToOwn::with((1, 2), |p| split_pair(p, true, true));
+        assert!(orig.is_none());
+        assert_eq!(split, Split::TookBoth);
+    }
+
+    #[test]
+    fn split_parts_deref_mut() {
+        let (orig, _) = RentToOwn::with((1, 2), |p| {
+            p.split(
+                |p| p,
+                |a, b| (a, b),
+                |a, b| {
+                    **a += 10;
+                    **b += 20;
+                },
+            )
+        });
+        assert_eq!(orig, Some((11, 22)));
+    }
+}
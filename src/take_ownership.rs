@@ -0,0 +1,176 @@
+//! `TakeOwnership<T>`: abstracting over owned and rented values.
+
+use super::{AsyncRentToOwn, Lease, RentToOwn};
+
+/// Something that gives access to a `T` value and may be turned into an owned
+/// `T`: either a `T` itself, one of this crate's rented wrappers around a `T`,
+/// or a `&mut Option<T>` holding one.
+///
+/// This allows writing a generic function once, and calling it with both owned
+/// and rented values.
+///
+/// Since this trait is implemented for every type, its methods are named so
+/// that they don't shadow common inherent methods like `Option::take` or
+/// `Vec::get` wherever the trait is in scope.
+///
+/// ```
+/// use rent_to_own::{RentToOwn, TakeOwnership};
+///
+/// fn consume_if_long<V: TakeOwnership<String>>(s: V) -> Option<String> {
+///     if s.value().len() > 3 {
+///         Some(s.take_ownership())
+///     } else {
+///         None
+///     }
+/// }
+///
+/// // With an owned value.
+/// assert_eq!(consume_if_long(String::from("hello")), Some(String::from("hello")));
+///
+/// // With a rented value.
+/// let (orig, taken) = RentToOwn::with(String::from("hi"), |s| consume_if_long(s));
+/// assert_eq!(orig, Some(String::from("hi")));
+/// assert_eq!(taken, None);
+/// ```
+pub trait TakeOwnership<T> {
+    /// Get a reference to the `T` value.
+    fn value(&self) -> &T;
+
+    /// Get a mutable reference to the `T` value.
+    fn value_mut(&mut self) -> &mut T;
+
+    /// Take ownership of the `T` value.
+    fn take_ownership(self) -> T;
+}
+
+impl<T> TakeOwnership<T> for T {
+    fn value(&self) -> &T {
+        self
+    }
+
+    fn value_mut(&mut self) -> &mut T {
+        self
+    }
+
+    fn take_ownership(self) -> T {
+        self
+    }
+}
+
+impl<'a, T> TakeOwnership<T> for &'a mut RentToOwn<'a, T> {
+    fn value(&self) -> &T {
+        self
+    }
+
+    fn value_mut(&mut self) -> &mut T {
+        self
+    }
+
+    fn take_ownership(self) -> T {
+        RentToOwn::take(self)
+    }
+}
+
+impl<'a, T> TakeOwnership<T> for AsyncRentToOwn<'a, T> {
+    fn value(&self) -> &T {
+        self
+    }
+
+    fn value_mut(&mut self) -> &mut T {
+        self
+    }
+
+    fn take_ownership(self) -> T {
+        AsyncRentToOwn::take(self)
+    }
+}
+
+impl<'a, T> TakeOwnership<T> for Lease<'a, T> {
+    fn value(&self) -> &T {
+        self
+    }
+
+    fn value_mut(&mut self) -> &mut T {
+        self
+    }
+
+    fn take_ownership(self) -> T {
+        self.keep()
+    }
+}
+
+/// A shim for code that already keeps its maybe-taken value in an `Option<T>`.
+///
+/// Unlike the other implementations, use-after-take can't be ruled out
+/// statically here: every method panics if the option is `None`.
+impl<T> TakeOwnership<T> for &mut Option<T> {
+    fn value(&self) -> &T {
+        self.as_ref().expect("TakeOwnership::value called on None")
+    }
+
+    fn value_mut(&mut self) -> &mut T {
+        self.as_mut()
+            .expect("TakeOwnership::value_mut called on None")
+    }
+
+    fn take_ownership(self) -> T {
+        self.take()
+            .expect("TakeOwnership::take_ownership called on None")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TakeOwnership;
+    use crate::RentToOwn;
+
+    fn bump_and_take_if_even<V: TakeOwnership<u32>>(mut v: V) -> Option<u32> {
+        *v.value_mut() += 1;
+        if *v.value() % 2 == 0 {
+            Some(v.take_ownership())
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn owned_value() {
+        assert_eq!(bump_and_take_if_even(1), Some(2));
+        assert_eq!(bump_and_take_if_even(2), None);
+    }
+
+    #[test]
+    fn rented_value() {
+        let (orig, taken) = RentToOwn::with(1, |x| bump_and_take_if_even(x));
+        assert_eq!(orig, None);
+        assert_eq!(taken, Some(2));
+
+        let (orig, taken) = RentToOwn::with(2, |x| bump_and_take_if_even(x));
+        assert_eq!(orig, Some(3));
+        assert_eq!(taken, None);
+    }
+
+    #[test]
+    fn option_value() {
+        let mut opt = Some(1);
+        assert_eq!(bump_and_take_if_even(&mut opt), Some(2));
+        assert_eq!(opt, None);
+
+        let mut opt = Some(2);
+        assert_eq!(bump_and_take_if_even(&mut opt), None);
+        assert_eq!(opt, Some(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "called on None")]
+    fn option_value_none_panics() {
+        bump_and_take_if_even(&mut None);
+    }
+
+    #[test]
+    fn leased_value() {
+        let (orig, taken) = RentToOwn::with(2, |x| bump_and_take_if_even(x.lease()));
+        assert_eq!(orig, Some(3));
+        assert_eq!(taken, None);
+    }
+}
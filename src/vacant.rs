@@ -0,0 +1,102 @@
+//! `Vacant<T>`: the empty slot left behind by `RentToOwn::take_slot`.
+
+use std::fmt;
+
+use super::RentToOwn;
+
+/// The empty slot of a `RentToOwn` whose inner value was taken with
+/// `take_slot`, which may be refilled with a new value.
+///
+/// Refilling the slot with `fill` consumes the `Vacant`, so it can be refilled
+/// at most once. If the `Vacant` is dropped without being refilled, then the
+/// `RentToOwn`'s value stays taken, just as if `take` had been used.
+///
+/// Construct one with `RentToOwn::take_slot`.
+#[must_use = "if the slot is not filled, the value stays taken"]
+pub struct Vacant<'a, T: 'a> {
+    // Invariant: the owner's slot is empty for as long as the `Vacant` exists.
+    owner: &'a mut RentToOwn<'a, T>,
+}
+
+impl<'a, T> fmt::Debug for Vacant<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Vacant").finish()
+    }
+}
+
+impl<'a, T> Vacant<'a, T> {
+    /// Put `value` into the empty slot, and get the refilled `RentToOwn` back
+    /// so that it can keep being used.
+    pub fn fill(self, value: T) -> &'a mut RentToOwn<'a, T> {
+        self.owner.inner.put(value);
+        self.owner
+    }
+}
+
+impl<'a, T> RentToOwn<'a, T> {
+    /// Take ownership of the inner `T` value, along with the `Vacant` slot it
+    /// leaves behind.
+    ///
+    /// This is useful for "take, transform, and put back" round trips: the
+    /// `Vacant` can be refilled with a new `T` value, which gives back a usable
+    /// `RentToOwn`, and whoever called `RentToOwn::with` gets the new value.
+    ///
+    /// Like `take`, the lifetime on the `self` reference makes the `RentToOwn`
+    /// unusable while its slot is vacant.
+    ///
+    /// ```
+    /// use rent_to_own::RentToOwn;
+    ///
+    /// enum State {
+    ///     Idle(u32),
+    ///     Running(u32),
+    /// }
+    ///
+    /// let (orig, _) = RentToOwn::with(State::Idle(5), |state| {
+    ///     let (old, vacant) = state.take_slot();
+    ///     let new = match old {
+    ///         State::Idle(n) => State::Running(n + 1),
+    ///         running => running,
+    ///     };
+    ///     vacant.fill(new);
+    /// });
+    ///
+    /// assert!(matches!(orig, Some(State::Running(6))));
+    /// ```
+    pub fn take_slot(&'a mut self) -> (T, Vacant<'a, T>) {
+        let value = unsafe { self.inner.take() };
+        (value, Vacant { owner: self })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RentToOwn;
+
+    #[test]
+    fn filled_slot_gives_back_new_value() {
+        let (orig, _) = RentToOwn::with(5, |x| {
+            let (old, vacant) = x.take_slot();
+            let x = vacant.fill(old + 1);
+            assert_eq!(**x, 6);
+        });
+        assert_eq!(orig, Some(6));
+    }
+
+    #[test]
+    fn unfilled_slot_stays_taken() {
+        let (orig, x) = RentToOwn::with(5, |x| x.take_slot().0);
+        assert!(orig.is_none());
+        assert_eq!(x, 5);
+    }
+
+    #[test]
+    fn filled_slot_can_be_taken_again() {
+        let (orig, x) = RentToOwn::with(5, |x| {
+            let (old, vacant) = x.take_slot();
+            vacant.fill(old + 1).take()
+        });
+        assert!(orig.is_none());
+        assert_eq!(x, 6);
+    }
+}
@@ -0,0 +1,65 @@
+//! Link-time checks that `RentToOwn`'s `Deref`, `DerefMut` and `take` have no
+//! reachable panic path.
+//!
+//! Each checked function runs its body while holding a `PanicGuard`, whose
+//! `Drop` calls a function that doesn't exist. The guard is forgotten on the
+//! normal return path, so the call is only emitted if the body can unwind, in
+//! which case linking fails with the guard's message.
+//!
+//! This relies on optimizations, so the checks are skipped in debug builds.
+//! Run them with:
+//!
+//! ```text
+//! cargo test --release --features no-panic --test no_panic
+//! ```
+
+#![cfg(all(feature = "no-panic", not(debug_assertions)))]
+
+use rent_to_own::RentToOwn;
+use std::mem;
+
+struct PanicGuard;
+
+impl Drop for PanicGuard {
+    fn drop(&mut self) {
+        extern "C" {
+            #[link_name = "\n\nERROR: a function checked by tests/no_panic.rs may panic\n\n"]
+            fn may_panic() -> !;
+        }
+        unsafe { may_panic() }
+    }
+}
+
+#[inline(always)]
+fn no_panic<R, F: FnOnce() -> R>(f: F) -> R {
+    let guard = PanicGuard;
+    let r = f();
+    mem::forget(guard);
+    r
+}
+
+#[inline(never)]
+fn deref<'a, 'b>(r: &'b RentToOwn<'a, String>) -> &'b String {
+    no_panic(move || &**r)
+}
+
+#[inline(never)]
+fn deref_mut<'a, 'b>(r: &'b mut RentToOwn<'a, String>) -> &'b mut String {
+    no_panic(move || &mut **r)
+}
+
+#[inline(never)]
+fn take<'a>(r: &'a mut RentToOwn<'a, String>) -> String {
+    no_panic(move || r.take())
+}
+
+#[test]
+fn deref_deref_mut_and_take_do_not_panic() {
+    let (orig, taken) = RentToOwn::with(String::from("hello"), |r| {
+        assert_eq!(deref(r), "hello");
+        deref_mut(r).make_ascii_uppercase();
+        take(r)
+    });
+    assert!(orig.is_none());
+    assert_eq!(taken, "HELLO");
+}